@@ -0,0 +1,260 @@
+//! Local stand-in for login.live.com and www.halowaypoint.com.
+//!
+//! It replays the Microsoft login form, the redirect chain ending with the
+//! `Auth=` cookie and the Halo Waypoint missions endpoint, serving the
+//! fixtures under `resources/halo_waypoint/service_records/`. The expected
+//! credentials are read from `HALO_WAYPOINT_LOGIN` and
+//! `HALO_WAYPOINT_PASSWORD`, same as the server.
+//!
+//! The client tests include this file as a module and start the mock with
+//! `server` on a free port.
+
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::process;
+use std::sync::Arc;
+
+const PPFT: &str = "mock-ppft";
+const COOKIE: &str = "MSPOK=mock-mspok";
+const CODE: &str = "mock-code";
+const AUTH: &str = "Auth=mock-auth";
+
+const RESOURCES: &str = "resources/halo_waypoint/service_records";
+
+/// Login and password the mock accepts.
+#[derive(Clone)]
+pub struct Credentials {
+    login: String,
+    password: String,
+}
+
+impl Credentials {
+    pub fn new(login: &str, password: &str) -> Self {
+        Self {
+            login: login.to_string(),
+            password: password.to_string(),
+        }
+    }
+}
+
+fn addr() -> Result<SocketAddr, String> {
+    let ip = [127, 0, 0, 1];
+    let port = match env::var("PORT") {
+        Ok(port) => port
+            .parse()
+            .map_err(|_| format!("PORT must be a port number, got {:?}", port))?,
+        Err(_) => 3001,
+    };
+
+    Ok(SocketAddr::from((ip, port)))
+}
+
+fn query(req: &Request<Body>) -> HashMap<String, String> {
+    req.uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| {
+            let mut pair = pair.splitn(2, '=');
+            match (pair.next(), pair.next()) {
+                (Some(key), Some(value)) => Some((key.to_string(), value.to_string())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn host(req: &Request<Body>) -> String {
+    req.headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_default()
+}
+
+fn authorize(req: &Request<Body>) -> Response<Body> {
+    let body = format!(
+        "<html><body><form><input type=\"hidden\" name=\"PPFT\" id=\"i0327\" value=\"{}\"/></form>\
+         <script>var ServerData = {{urlPost:'http://{}/ppsecure/post.srf'}};</script></body></html>",
+        PPFT,
+        host(req),
+    );
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::SET_COOKIE, format!("{}; path=/; secure", COOKIE))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn post(req: Request<Body>, credentials: &Credentials) -> Response<Body> {
+    let host = host(&req);
+    let cookie = req
+        .headers()
+        .get(header::COOKIE)
+        .and_then(|cookie| cookie.to_str().ok())
+        .map(|cookie| cookie.contains(COOKIE))
+        .unwrap_or(false);
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .map(|body| String::from_utf8_lossy(&body).to_string())
+        .unwrap_or_default();
+
+    let expected = format!(
+        "login={}&passwd={}&PPFT={}",
+        credentials.login, credentials.password, PPFT,
+    );
+
+    match (cookie, body == expected) {
+        (true, true) => Response::builder()
+            .status(StatusCode::FOUND)
+            .header(
+                header::LOCATION,
+                format!("http://{}/auth/callback?code={}", host, CODE),
+            )
+            .body(Body::empty())
+            .unwrap(),
+        _ => Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from("Your account or password is incorrect."))
+            .unwrap(),
+    }
+}
+
+fn callback(req: &Request<Body>) -> Response<Body> {
+    match query(req).get("code").map(String::as_str) {
+        Some(CODE) => Response::builder()
+            .status(StatusCode::FOUND)
+            .header(header::LOCATION, "/en-us")
+            .header(
+                header::SET_COOKIE,
+                format!("{}; path=/; secure; HttpOnly", AUTH),
+            )
+            .body(Body::empty())
+            .unwrap(),
+        _ => status(StatusCode::UNAUTHORIZED),
+    }
+}
+
+fn fixture(game: &str, campaign_mode: &str) -> Option<String> {
+    let game = match game {
+        "HaloCombatEvolved" => "halo",
+        "Halo2" => "halo_2",
+        "Halo3" => "halo_3",
+        "Halo3Odst" => "halo_3_odst",
+        "HaloReach" => "halo_reach",
+        "Halo4" => "halo_4",
+        _ => return None,
+    };
+
+    let campaign_mode = match campaign_mode {
+        "Solo" => "solo",
+        "Coop" => "coop",
+        _ => return None,
+    };
+
+    fs::read_to_string(format!("{}/{}_{}.html", RESOURCES, game, campaign_mode)).ok()
+}
+
+fn missions(req: &Request<Body>) -> Response<Body> {
+    let authenticated = req
+        .headers()
+        .get(header::COOKIE)
+        .and_then(|cookie| cookie.to_str().ok())
+        .map(|cookie| cookie.contains(AUTH))
+        .unwrap_or(false);
+
+    if !authenticated {
+        return status(StatusCode::UNAUTHORIZED);
+    }
+
+    let query = query(req);
+    let game = query.get("game").map(String::as_str).unwrap_or_default();
+    let campaign_mode = query
+        .get("campaignMode")
+        .map(String::as_str)
+        .unwrap_or_default();
+
+    match fixture(game, campaign_mode) {
+        Some(body) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(body))
+            .unwrap(),
+        None => status(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn route(
+    req: Request<Body>,
+    credentials: Arc<Credentials>,
+) -> Result<Response<Body>, hyper::Error> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let segments = path.split('/').skip(1).collect::<Vec<&str>>();
+
+    let res = match (method, segments.as_slice()) {
+        (Method::GET, ["oauth20_authorize.srf"]) => authorize(&req),
+        (Method::POST, ["ppsecure", "post.srf"]) => post(req, &credentials).await,
+        (Method::GET, ["auth", "callback"]) => callback(&req),
+        (
+            Method::GET,
+            [_, "games", "halo-the-master-chief-collection", _, "service-records", "players", _, "missions"],
+        ) => missions(&req),
+        _ => status(StatusCode::NOT_FOUND),
+    };
+
+    Ok(res)
+}
+
+/// Binds the mock to `addr` (port 0 picks a free port) and returns the bound
+/// address with the future serving requests.
+pub fn server(
+    addr: SocketAddr,
+    credentials: Credentials,
+) -> Result<(SocketAddr, impl Future<Output = Result<(), hyper::Error>>), hyper::Error> {
+    let credentials = Arc::new(credentials);
+    let new_service = make_service_fn(move |_| {
+        let credentials = credentials.clone();
+        async move { Ok::<_, hyper::Error>(service_fn(move |req| route(req, credentials.clone()))) }
+    });
+
+    let server = Server::try_bind(&addr)?.serve(new_service);
+
+    Ok((server.local_addr(), server))
+}
+
+#[tokio::main]
+async fn main() {
+    let credentials = Credentials::new(
+        &env::var("HALO_WAYPOINT_LOGIN").unwrap_or_default(),
+        &env::var("HALO_WAYPOINT_PASSWORD").unwrap_or_default(),
+    );
+    let (addr, server) = addr()
+        .and_then(|addr| server(addr, credentials).map_err(|err| err.to_string()))
+        .unwrap_or_else(|err| {
+            eprintln!("mock_halo_waypoint: {}", err);
+            process::exit(1)
+        });
+    println!("Listening on http://{}", addr);
+
+    if let Err(e) = server.await {
+        eprintln!("server error: {}", e)
+    }
+}
@@ -1,5 +1,18 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+use strum_macros::IntoStaticStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
 pub enum CampaignMode {
     Solo,
     Coop,
 }
+
+#[cfg(test)]
+mod campaign_mode_test {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        assert!(CampaignMode::Solo < CampaignMode::Coop);
+    }
+}
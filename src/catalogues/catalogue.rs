@@ -0,0 +1,608 @@
+use itertools::Itertools;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::hash::Hash;
+use time::{Date, Time};
+
+use crate::chainable::Chainable;
+use crate::error::{CatalogueError, Error};
+use crate::games::dao::InMemoryGamesDao;
+use crate::games::game::{Game, GameId};
+use crate::missions::dao::InMemoryMissionsDao;
+use crate::missions::mission::{Mission, MissionId, MissionKind};
+use crate::missions::par::Par;
+
+#[derive(Deserialize)]
+struct CatalogueFile {
+    #[serde(default)]
+    par_versions: Vec<CatalogueParVersion>,
+    games: Vec<CatalogueGame>,
+    missions: Vec<CatalogueMission>,
+    #[serde(default)]
+    par_revisions: Vec<CatalogueParRevision>,
+}
+
+#[derive(Deserialize)]
+struct CatalogueParVersion {
+    version: String,
+    effective_from: String,
+}
+
+#[derive(Deserialize)]
+struct CatalogueGame {
+    id: i32,
+    name: String,
+    short_code: String,
+    release_year: i32,
+    developer: String,
+    halo_waypoint_key: String,
+    menu_order: i32,
+}
+
+#[derive(Deserialize)]
+struct CatalogueMission {
+    game_id: i32,
+    id: i32,
+    name: String,
+    par_time: Option<String>,
+    par_score: Option<i32>,
+    kind: Option<String>,
+    ordinal: i32,
+    halo_waypoint_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct CatalogueParRevision {
+    game_id: i32,
+    mission_id: i32,
+    version: String,
+    par_time: Option<String>,
+    par_score: Option<i32>,
+}
+
+/// Games and missions, validated as a whole so DAOs never see a partial catalogue.
+pub struct Catalogue {
+    games_dao: InMemoryGamesDao,
+    missions_dao: InMemoryMissionsDao,
+}
+
+impl Catalogue {
+    const DEFAULT: &'static str = include_str!("../../resources/catalogue.toml");
+    const LAUNCH_PAR_VERSION: &'static str = "launch";
+    const LAUNCH_PAR_EFFECTIVE_FROM: &'static str = "2014-11-11";
+
+    pub fn games_dao(&self) -> &InMemoryGamesDao {
+        &self.games_dao
+    }
+
+    pub fn missions_dao(&self) -> &InMemoryMissionsDao {
+        &self.missions_dao
+    }
+
+    pub fn try_from_str(catalogue: &str) -> Result<Self, Error> {
+        let file: CatalogueFile = toml::from_str(catalogue)
+            .map_err(|err| CatalogueError::Parse(err.to_string()).pipe(Error::Catalogue))?;
+
+        let errors = Self::validate(&file);
+        if !errors.is_empty() {
+            return errors
+                .into_iter()
+                .map(Error::Catalogue)
+                .collect::<Vec<Error>>()
+                .pipe(Error::List)
+                .pipe(Err);
+        }
+
+        let missions = file
+            .missions
+            .iter()
+            .map(|mission| {
+                Mission::new(
+                    GameId::new(mission.game_id),
+                    MissionId::new(mission.id),
+                    &mission.name,
+                    Self::pars(&file, mission),
+                    mission
+                        .kind
+                        .as_deref()
+                        .map_or(Some(MissionKind::Level), Self::parse_kind)
+                        .unwrap_or(MissionKind::Level),
+                    mission.ordinal,
+                )
+            })
+            .collect::<Vec<Mission>>();
+
+        // Cutscenes don't count towards a campaign.
+        let games = file
+            .games
+            .iter()
+            .map(|game| {
+                let mission_count = missions
+                    .iter()
+                    .filter(|mission| mission.game_id() == GameId::new(game.id))
+                    .filter(|mission| mission.is_playable())
+                    .count();
+
+                Game::new(
+                    GameId::new(game.id),
+                    &game.name,
+                    &game.short_code,
+                    game.release_year,
+                    &game.developer,
+                    &game.halo_waypoint_key,
+                    mission_count as i32,
+                    game.menu_order,
+                )
+            })
+            .collect();
+
+        let halo_waypoint_ids = file
+            .missions
+            .iter()
+            .filter_map(|mission| {
+                mission.halo_waypoint_id.map(|halo_waypoint_id| {
+                    (
+                        GameId::new(mission.game_id),
+                        MissionId::new(mission.id),
+                        halo_waypoint_id,
+                    )
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            games_dao: InMemoryGamesDao::new(games),
+            missions_dao: InMemoryMissionsDao::new(missions, halo_waypoint_ids),
+        })
+    }
+
+    /// Par versions oldest first, defaulting to the MCC launch when the file declares none.
+    fn par_versions(file: &CatalogueFile) -> Vec<(String, Date)> {
+        let versions = if file.par_versions.is_empty() {
+            vec![(Self::LAUNCH_PAR_VERSION, Self::LAUNCH_PAR_EFFECTIVE_FROM)]
+        } else {
+            file.par_versions
+                .iter()
+                .map(|version| (version.version.as_str(), version.effective_from.as_str()))
+                .collect()
+        };
+
+        versions
+            .into_iter()
+            .filter_map(|(version, effective_from)| {
+                Self::parse_date(effective_from).map(|date| (version.to_string(), date))
+            })
+            .sorted_by_key(|(_, date)| *date)
+            .collect()
+    }
+
+    /// The mission's own values apply from the first version, each revision from its version onwards.
+    fn pars(file: &CatalogueFile, mission: &CatalogueMission) -> Vec<Par> {
+        let mut time = mission.par_time.as_deref().and_then(Self::parse_time);
+        let mut score = mission.par_score;
+
+        Self::par_versions(file)
+            .into_iter()
+            .map(|(version, effective_from)| {
+                let revision = file.par_revisions.iter().find(|revision| {
+                    revision.game_id == mission.game_id
+                        && revision.mission_id == mission.id
+                        && revision.version == version
+                });
+
+                if let Some(revision) = revision {
+                    time = revision
+                        .par_time
+                        .as_deref()
+                        .and_then(Self::parse_time)
+                        .or(time);
+                    score = revision.par_score.or(score);
+                }
+
+                Par::new(&version, effective_from, time, score)
+            })
+            .collect()
+    }
+
+    fn parse_date(date: &str) -> Option<Date> {
+        Date::parse(date, "%F").ok()
+    }
+
+    fn parse_time(time: &str) -> Option<Time> {
+        Time::parse(time, "%T").ok()
+    }
+
+    fn parse_kind(kind: &str) -> Option<MissionKind> {
+        match kind {
+            "level" => Some(MissionKind::Level),
+            "cutscene" => Some(MissionKind::Cutscene),
+            "bonus" => Some(MissionKind::Bonus),
+            _ => None,
+        }
+    }
+
+    fn duplicates<T: Eq + Hash + Copy>(items: impl Iterator<Item = T>) -> Vec<T> {
+        let mut seen = HashSet::new();
+        items.filter(|item| !seen.insert(*item)).unique().collect()
+    }
+
+    fn validate(file: &CatalogueFile) -> Vec<CatalogueError> {
+        let duplicate_games = file
+            .games
+            .iter()
+            .map(|game| game.id)
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(CatalogueError::DuplicateGameId);
+
+        let duplicate_game_short_codes = file
+            .games
+            .iter()
+            .map(|game| game.short_code.as_str())
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(|short_code| CatalogueError::DuplicateGameShortCode(short_code.to_string()));
+
+        let duplicate_missions = file
+            .missions
+            .iter()
+            .map(|mission| (mission.game_id, mission.id))
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(|(game_id, id)| CatalogueError::DuplicateMissionId(game_id, id));
+
+        let duplicate_mission_ordinals = file
+            .missions
+            .iter()
+            .map(|mission| (mission.game_id, mission.ordinal))
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(|(game_id, ordinal)| CatalogueError::DuplicateMissionOrdinal(game_id, ordinal));
+
+        let missing_games = file
+            .missions
+            .iter()
+            .map(|mission| mission.game_id)
+            .unique()
+            .filter(|game_id| file.games.iter().all(|game| game.id != *game_id))
+            .map(CatalogueError::MissingGame);
+
+        let invalid_par_times = file
+            .missions
+            .iter()
+            .filter_map(|mission| mission.par_time.as_ref())
+            .filter(|par_time| Self::parse_time(par_time).is_none())
+            .map(|par_time| CatalogueError::InvalidParTime(par_time.to_string()));
+
+        let duplicate_par_versions = file
+            .par_versions
+            .iter()
+            .map(|version| version.version.as_str())
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(|version| CatalogueError::DuplicateParVersion(version.to_string()));
+
+        let invalid_effective_froms = file
+            .par_versions
+            .iter()
+            .map(|version| version.effective_from.as_str())
+            .filter(|effective_from| Self::parse_date(effective_from).is_none())
+            .map(|effective_from| CatalogueError::InvalidEffectiveFrom(effective_from.to_string()));
+
+        let par_versions = Self::par_versions(file);
+        let unknown_par_versions = file
+            .par_revisions
+            .iter()
+            .map(|revision| revision.version.as_str())
+            .unique()
+            .filter(|version| par_versions.iter().all(|(v, _)| v != version))
+            .map(|version| CatalogueError::UnknownParVersion(version.to_string()));
+
+        let missing_missions = file
+            .par_revisions
+            .iter()
+            .map(|revision| (revision.game_id, revision.mission_id))
+            .unique()
+            .filter(|(game_id, id)| {
+                file.missions
+                    .iter()
+                    .all(|mission| mission.game_id != *game_id || mission.id != *id)
+            })
+            .map(|(game_id, id)| CatalogueError::MissingMission(game_id, id));
+
+        let invalid_revision_par_times = file
+            .par_revisions
+            .iter()
+            .filter_map(|revision| revision.par_time.as_ref())
+            .filter(|par_time| Self::parse_time(par_time).is_none())
+            .map(|par_time| CatalogueError::InvalidParTime(par_time.to_string()));
+
+        let unknown_mission_kinds = file
+            .missions
+            .iter()
+            .filter_map(|mission| mission.kind.as_ref())
+            .filter(|kind| Self::parse_kind(kind).is_none())
+            .map(|kind| CatalogueError::UnknownMissionKind(kind.to_string()));
+
+        let duplicate_halo_waypoint_ids = file
+            .missions
+            .iter()
+            .filter_map(|mission| mission.halo_waypoint_id.map(|id| (mission.game_id, id)))
+            .pipe(Self::duplicates)
+            .into_iter()
+            .map(|(game_id, id)| CatalogueError::DuplicateHaloWaypointId(game_id, id));
+
+        duplicate_games
+            .chain(duplicate_game_short_codes)
+            .chain(duplicate_missions)
+            .chain(duplicate_mission_ordinals)
+            .chain(missing_games)
+            .chain(invalid_par_times)
+            .chain(duplicate_par_versions)
+            .chain(invalid_effective_froms)
+            .chain(unknown_par_versions)
+            .chain(missing_missions)
+            .chain(invalid_revision_par_times)
+            .chain(unknown_mission_kinds)
+            .chain(duplicate_halo_waypoint_ids)
+            .collect()
+    }
+
+    pub fn default() -> Self {
+        Self::try_from_str(Self::DEFAULT).expect("Invalid default catalogue")
+    }
+}
+
+#[cfg(test)]
+mod catalogue_test {
+    use super::*;
+    use crate::games::dao::GamesDao;
+    use crate::missions::dao::MissionsDao;
+
+    const GAME: &str = r#"
+            [[games]]
+            id = 1
+            name = "Halo"
+            short_code = "haloce"
+            release_year = 2001
+            developer = "Bungie"
+            halo_waypoint_key = "HaloCombatEvolved"
+            menu_order = 2
+    "#;
+
+    /// Catalogue with `GAME` and the entries in `toml`.
+    fn with_game(toml: &str) -> Result<Catalogue, Error> {
+        Catalogue::try_from_str(&format!("{}{}", GAME, toml))
+    }
+
+    #[test]
+    fn default() {
+        let catalogue = Catalogue::default();
+
+        assert_eq!(catalogue.games_dao().all().len(), 6);
+        assert_eq!(
+            catalogue
+                .missions_dao()
+                .all_by_game_id(GameId::new(3))
+                .len(),
+            11
+        );
+    }
+
+    #[test]
+    fn mission_count() {
+        let catalogue = Catalogue::default();
+
+        assert_eq!(
+            catalogue
+                .games_dao()
+                .find_by_id(GameId::new(5))
+                .map(|game| game.mission_count()),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn missing_game() {
+        let res = Catalogue::try_from_str(
+            r#"
+            games = []
+
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+            "#,
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [Error::Catalogue(CatalogueError::MissingGame(1))])
+        ));
+    }
+
+    #[test]
+    fn duplicates() {
+        let res = with_game(&format!(
+            "{}{}",
+            GAME,
+            r#"
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+            halo_waypoint_id = 0
+
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Halo"
+            ordinal = 2
+            halo_waypoint_id = 0
+            "#,
+        ));
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Catalogue(CatalogueError::DuplicateGameId(1)),
+                Error::Catalogue(CatalogueError::DuplicateGameShortCode(_)),
+                Error::Catalogue(CatalogueError::DuplicateMissionId(1, 1)),
+                Error::Catalogue(CatalogueError::DuplicateHaloWaypointId(1, 0)),
+            ])
+        ));
+    }
+
+    #[test]
+    fn kind() {
+        let catalogue = Catalogue::default();
+        let kinds = catalogue
+            .missions_dao()
+            .all_by_game_id(GameId::new(5))
+            .iter()
+            .map(|mission| mission.kind())
+            .collect::<Vec<MissionKind>>();
+
+        assert_eq!(kinds.first(), Some(&MissionKind::Cutscene));
+        assert_eq!(kinds.get(1), Some(&MissionKind::Level));
+        assert_eq!(kinds.last(), Some(&MissionKind::Bonus));
+    }
+
+    #[test]
+    fn unknown_kind() {
+        let res = with_game(
+            r#"
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+            kind = "cinematic"
+            "#,
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [Error::Catalogue(CatalogueError::UnknownMissionKind(_))])
+        ));
+    }
+
+    #[test]
+    fn invalid_par_time() {
+        let res = with_game(
+            r#"
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+            par_time = "15 minutes"
+            "#,
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [Error::Catalogue(CatalogueError::InvalidParTime(_))])
+        ));
+    }
+
+    #[test]
+    fn pars() {
+        let catalogue = with_game(
+            r#"
+            [[par_versions]]
+            version = "launch"
+            effective_from = "2014-11-11"
+
+            [[par_versions]]
+            version = "season-1"
+            effective_from = "2015-06-01"
+
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+            par_time = "00:15:00"
+            par_score = 17000
+
+            [[par_revisions]]
+            game_id = 1
+            mission_id = 1
+            version = "season-1"
+            par_score = 15000
+            "#,
+        )
+        .unwrap();
+
+        let mission = catalogue
+            .missions_dao()
+            .find_by_game_id_and_id(GameId::new(1), MissionId::new(1))
+            .unwrap();
+
+        assert_eq!(
+            mission.par_by_version("launch").and_then(|par| par.score()),
+            Some(17000)
+        );
+        assert_eq!(
+            mission
+                .par_by_version("season-1")
+                .map(|par| (par.time(), par.score())),
+            Some((Some(time::time!(00:15:00)), Some(15000)))
+        );
+        assert_eq!(mission.par_score(), Some(15000));
+    }
+
+    #[test]
+    fn default_par_version() {
+        let catalogue = Catalogue::default();
+        let pars = catalogue
+            .missions_dao()
+            .find_by_game_id_and_id(GameId::new(1), MissionId::new(1))
+            .map(|mission| mission.pars())
+            .unwrap_or_default();
+
+        assert_eq!(
+            pars.iter()
+                .map(|par| (par.version(), par.effective_from()))
+                .collect::<Vec<(String, Date)>>(),
+            vec![("launch".to_string(), time::date!(2014 - 11 - 11))]
+        );
+    }
+
+    #[test]
+    fn unknown_par_version() {
+        let res = with_game(
+            r#"
+            [[missions]]
+            game_id = 1
+            id = 1
+            name = "Pillar of Autumn"
+            ordinal = 1
+
+            [[par_revisions]]
+            game_id = 1
+            mission_id = 2
+            version = "season-1"
+            "#,
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Catalogue(CatalogueError::UnknownParVersion(_)),
+                Error::Catalogue(CatalogueError::MissingMission(1, 2)),
+            ])
+        ));
+    }
+
+    #[test]
+    fn invalid_toml() {
+        assert!(matches!(
+            Catalogue::try_from_str("games = "),
+            Err(Error::Catalogue(CatalogueError::Parse(_)))
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use tracing::{error, info};
+
+use crate::catalogues::catalogue::Catalogue;
+use crate::chainable::Chainable;
+use crate::error::{CatalogueError, Error};
+
+/// Catalogue read from disk, optionally reloaded when the file's modification time changes.
+pub struct FileCatalogue {
+    path: PathBuf,
+    reload: bool,
+    state: RwLock<(Option<SystemTime>, Arc<Catalogue>)>,
+}
+
+impl FileCatalogue {
+    pub fn try_from_path(path: &Path, reload: bool) -> Result<Self, Error> {
+        let modified = Self::modified(path);
+        let catalogue = Self::read(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            reload,
+            state: RwLock::new((modified, Arc::new(catalogue))),
+        })
+    }
+
+    pub fn current(&self) -> Arc<Catalogue> {
+        if self.reload {
+            self.reload_if_modified();
+        }
+
+        self.state.read().unwrap().1.clone()
+    }
+
+    fn reload_if_modified(&self) {
+        let modified = Self::modified(&self.path);
+        if modified == self.state.read().unwrap().0 {
+            return;
+        }
+
+        // A broken edit keeps the previous catalogue until the file is fixed.
+        match Self::read(&self.path) {
+            Ok(catalogue) => {
+                info!(path = %self.path.display(), "catalogue reloaded");
+                *self.state.write().unwrap() = (modified, Arc::new(catalogue));
+            }
+            Err(err) => {
+                error!(path = %self.path.display(), error = ?err, "invalid catalogue, keeping the previous one");
+                self.state.write().unwrap().0 = modified;
+            }
+        }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn read(path: &Path) -> Result<Catalogue, Error> {
+        fs::read_to_string(path)
+            .map_err(|err| CatalogueError::Io(err.to_string()).pipe(Error::Catalogue))
+            .and_then(|catalogue| Catalogue::try_from_str(&catalogue))
+    }
+}
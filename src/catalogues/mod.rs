@@ -0,0 +1,2 @@
+pub mod catalogue;
+pub mod file_catalogue;
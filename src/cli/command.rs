@@ -0,0 +1,257 @@
+use itertools::{Either, Itertools};
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+use crate::campaign_modes::campaign_mode::CampaignMode;
+use crate::chainable::Chainable;
+use crate::cli::format::Format;
+use crate::cli::row::{MissionRow, RunRow};
+use crate::configs::config::Config;
+use crate::error::Error;
+use crate::gamertags::gamertag::Gamertag;
+use crate::games::game::Game;
+use crate::graphql::context::Context;
+use crate::halo_waypoint::requests::service_record::GetServiceRecordResponse;
+use crate::locales::locale::Locale;
+use crate::platforms::platform::Platform;
+use crate::service_records::service_record::ServiceRecordRun;
+
+/// Serves the GraphQL API when no command is given.
+#[derive(StructOpt)]
+#[structopt(name = "halomcc-run")]
+pub struct Cli {
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+pub enum Command {
+    /// Fetches a player's service record from Halo Waypoint
+    Fetch {
+        gamertag: String,
+        /// Game short code, every game when omitted
+        #[structopt(long)]
+        game: Option<String>,
+        /// solo or coop, both when omitted
+        #[structopt(long, parse(try_from_str = parse_campaign_mode))]
+        mode: Option<CampaignMode>,
+        /// table, json or csv
+        #[structopt(long, default_value = "table")]
+        format: Format,
+    },
+    /// Parses a missions page saved from Halo Waypoint
+    Parse {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+        /// table, json or csv
+        #[structopt(long, default_value = "table")]
+        format: Format,
+    },
+    /// Lists the games and missions of the catalogue
+    Catalogue {
+        /// table, json or csv
+        #[structopt(long, default_value = "table")]
+        format: Format,
+    },
+}
+
+fn parse_campaign_mode(campaign_mode: &str) -> Result<CampaignMode, String> {
+    match campaign_mode {
+        "solo" => Ok(CampaignMode::Solo),
+        "coop" => Ok(CampaignMode::Coop),
+        campaign_mode => Err(format!(
+            "unknown mode {}, expected solo or coop",
+            campaign_mode
+        )),
+    }
+}
+
+impl Command {
+    /// Prints the result on stdout, logs stay on stderr.
+    ///
+    /// A parsed page with inconsistencies still prints its runs, then fails with them.
+    pub async fn run(self, config: &Config) -> Result<(), Error> {
+        let context = Context::new(config)?;
+
+        let (output, inconsistencies) = match self {
+            Self::Fetch {
+                gamertag,
+                game,
+                mode,
+                format,
+            } => fetch(&context, &gamertag, game.as_deref(), mode)
+                .await?
+                .pipe(|rows| (format.render(&rows), vec![])),
+            Self::Parse { file, format } => {
+                let (rows, inconsistencies) = parse(&context, &file)?;
+                (format.render(&rows), inconsistencies)
+            }
+            Self::Catalogue { format } => {
+                catalogue(&context).pipe(|rows| (format.render(&rows), vec![]))
+            }
+        };
+
+        print!("{}", output);
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::List(inconsistencies))
+        }
+    }
+}
+
+async fn fetch(
+    context: &Context,
+    gamertag: &str,
+    game: Option<&str>,
+    mode: Option<CampaignMode>,
+) -> Result<Vec<RunRow>, Error> {
+    let player = Gamertag::try_from_str(gamertag)?;
+    let games = match game {
+        Some(short_code) => context
+            .games_dao()
+            .find_by_short_code(short_code)
+            .map(|game| vec![game])
+            .ok_or_else(|| Error::InvalidArgument(format!("unknown game {}", short_code)))?,
+        None => games(context),
+    };
+
+    let mut rows = vec![];
+    for game in games {
+        // The inconsistencies are already logged on stderr.
+        let service_records = context
+            .service_records_doa()
+            .find_by_player_and_game(player.clone(), game, Platform::default(), Locale::default())
+            .await?
+            .into_records();
+
+        rows.extend(
+            service_records
+                .iter()
+                .flat_map(|service_record| service_record.runs())
+                .filter(|run| mode.map_or(true, |mode| run.campaign_mode() == mode))
+                .map(|run| RunRow::new(&run, context.games_dao(), context.missions_dao())),
+        );
+    }
+
+    Ok(rows)
+}
+
+fn parse(context: &Context, file: &Path) -> Result<(Vec<RunRow>, Vec<Error>), Error> {
+    fs::read_to_string(file)
+        .map_err(|err| Error::InvalidArgument(format!("{}: {}", file.display(), err)))?
+        .pipe(|html| parse_html(context, &html))
+}
+
+/// Unknown missions are skipped, like the server does, and returned as inconsistencies.
+fn parse_html(context: &Context, html: &str) -> Result<(Vec<RunRow>, Vec<Error>), Error> {
+    let (runs, inconsistencies): (Vec<ServiceRecordRun>, Vec<Error>) =
+        GetServiceRecordResponse::try_from_html(html)?
+            .to_internal(context.games_dao(), context.missions_dao())
+            .into_iter()
+            .partition_map(|run| match run {
+                Ok((g, m, c, d, t, s)) => Either::Left(ServiceRecordRun::new(g, m, c, d, t, s)),
+                Err(err) => Either::Right(err),
+            });
+
+    let rows = runs
+        .iter()
+        .map(|run| RunRow::new(run, context.games_dao(), context.missions_dao()))
+        .collect::<Vec<RunRow>>();
+
+    Ok((rows, inconsistencies))
+}
+
+fn catalogue(context: &Context) -> Vec<MissionRow> {
+    games(context)
+        .iter()
+        .flat_map(|game| {
+            context
+                .missions_dao()
+                .all_by_game_id(game.id())
+                .iter()
+                .map(|mission| MissionRow::new(game, mission, context.missions_dao()))
+                .collect::<Vec<MissionRow>>()
+        })
+        .collect()
+}
+
+fn games(context: &Context) -> Vec<Game> {
+    context
+        .games_dao()
+        .all()
+        .into_iter()
+        .sorted_by_key(|game| game.menu_order())
+        .collect()
+}
+
+#[cfg(test)]
+mod command_test {
+    use super::*;
+    use crate::error::HaloWaypointError;
+
+    const HALO_SOLO: &str = "resources/halo_waypoint/service_records/halo_solo.html";
+
+    #[tokio::test]
+    async fn parse_fixture() {
+        let res = Command::Parse {
+            file: PathBuf::from(HALO_SOLO),
+            format: Format::Csv,
+        }
+        .run(&Config::default())
+        .await;
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn parse_unknown_mission() {
+        let context = Context::new(&Config::default()).unwrap();
+        let html = fs::read_to_string(HALO_SOLO).unwrap();
+        let (rows, _) = parse_html(&context, &html).unwrap();
+
+        let (unknown_rows, inconsistencies) = parse_html(
+            &context,
+            &html.replacen("data-mission-id=\"0\"", "data-mission-id=\"999\"", 1),
+        )
+        .unwrap();
+
+        assert_eq!(unknown_rows.len(), rows.len() - 1);
+        assert!(matches!(
+            inconsistencies.as_slice(),
+            [Error::HaloWaypoint(HaloWaypointError::UnknownMissionId(
+                999
+            ))]
+        ));
+    }
+
+    #[test]
+    fn parse_args() {
+        let cli = Cli::from_iter(vec![
+            "halomcc-run",
+            "fetch",
+            "Master Chief",
+            "--game",
+            "halo3",
+            "--mode",
+            "coop",
+            "--format",
+            "csv",
+        ]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Fetch {
+                game: Some(_),
+                mode: Some(CampaignMode::Coop),
+                format: Format::Csv,
+                ..
+            })
+        ));
+        assert!(Cli::from_iter(vec!["halomcc-run"]).command.is_none());
+        assert!(
+            Cli::from_iter_safe(vec!["halomcc-run", "fetch", "John117", "--mode", "duo"]).is_err()
+        );
+    }
+}
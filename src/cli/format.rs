@@ -0,0 +1,153 @@
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Rows printable as an aligned table, next to their JSON and CSV serializations.
+pub trait Tabular {
+    fn headers() -> Vec<&'static str>;
+    fn cells(&self) -> Vec<String>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn render<T: Serialize + Tabular>(self, rows: &[T]) -> String {
+        match self {
+            Self::Table => Self::table(rows),
+            Self::Json => {
+                serde_json::to_string_pretty(rows).expect("rows serialize to JSON") + "\n"
+            }
+            Self::Csv => Self::csv(rows),
+        }
+    }
+
+    fn table<T: Tabular>(rows: &[T]) -> String {
+        let headers = T::headers()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        let lines = std::iter::once(headers)
+            .chain(rows.iter().map(Tabular::cells))
+            .collect::<Vec<Vec<String>>>();
+        let widths = (0..T::headers().len())
+            .map(|column| {
+                lines
+                    .iter()
+                    .map(|cells| cells[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<usize>>();
+
+        lines
+            .iter()
+            .map(|cells| {
+                cells
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect::<Vec<String>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+                    + "\n"
+            })
+            .collect()
+    }
+
+    fn csv<T: Serialize + Tabular>(rows: &[T]) -> String {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        // Headers come from the field names, so an empty export still has them.
+        if rows.is_empty() {
+            writer
+                .write_record(T::headers())
+                .expect("header written to memory");
+        }
+        rows.iter()
+            .for_each(|row| writer.serialize(row).expect("row written to memory"));
+
+        writer
+            .into_inner()
+            .map(|csv| String::from_utf8_lossy(&csv).to_string())
+            .expect("CSV written to memory")
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            format => Err(format!(
+                "unknown format {}, expected table, json or csv",
+                format
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod format_test {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        score: i32,
+    }
+
+    impl Tabular for Row {
+        fn headers() -> Vec<&'static str> {
+            vec!["name", "score"]
+        }
+
+        fn cells(&self) -> Vec<String> {
+            vec![self.name.clone(), self.score.to_string()]
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: "Halo".to_string(),
+                score: 23520,
+            },
+            Row {
+                name: "The Silent Cartographer".to_string(),
+                score: 7,
+            },
+        ]
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            Format::Table.render(&rows()),
+            "name                     score\n\
+             Halo                     23520\n\
+             The Silent Cartographer  7\n"
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            Format::Csv.render(&rows()),
+            "name,score\nHalo,23520\nThe Silent Cartographer,7\n"
+        );
+        assert_eq!(Format::Csv.render::<Row>(&[]), "name,score\n");
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
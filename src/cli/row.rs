@@ -0,0 +1,126 @@
+use serde::Serialize;
+
+use crate::cli::format::Tabular;
+use crate::games::dao::GamesDao;
+use crate::games::game::Game;
+use crate::missions::dao::MissionsDao;
+use crate::missions::mission::Mission;
+use crate::service_records::service_record::ServiceRecordRun;
+
+#[derive(Serialize)]
+pub struct RunRow {
+    game: String,
+    mission_id: i32,
+    mission: String,
+    campaign_mode: &'static str,
+    difficulty: &'static str,
+    time: String,
+    score: i32,
+}
+
+impl RunRow {
+    pub fn new(
+        run: &ServiceRecordRun,
+        games_dao: &dyn GamesDao,
+        missions_dao: &dyn MissionsDao,
+    ) -> Self {
+        Self {
+            game: games_dao
+                .find_by_id(run.game_id())
+                .map(|game| game.short_code())
+                .unwrap_or_default(),
+            mission_id: *run.mission_id().value(),
+            mission: missions_dao
+                .find_by_game_id_and_id(run.game_id(), run.mission_id())
+                .map(|mission| mission.name())
+                .unwrap_or_default(),
+            campaign_mode: run.campaign_mode().into(),
+            difficulty: run.difficulty().into(),
+            time: run.time().format("%T"),
+            score: run.score(),
+        }
+    }
+}
+
+impl Tabular for RunRow {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "game",
+            "mission_id",
+            "mission",
+            "campaign_mode",
+            "difficulty",
+            "time",
+            "score",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.game.clone(),
+            self.mission_id.to_string(),
+            self.mission.clone(),
+            self.campaign_mode.to_string(),
+            self.difficulty.to_string(),
+            self.time.clone(),
+            self.score.to_string(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct MissionRow {
+    game: String,
+    ordinal: i32,
+    mission_id: i32,
+    mission: String,
+    kind: &'static str,
+    par_time: Option<String>,
+    par_score: Option<i32>,
+    halo_waypoint_id: Option<i32>,
+}
+
+impl MissionRow {
+    pub fn new(game: &Game, mission: &Mission, missions_dao: &dyn MissionsDao) -> Self {
+        Self {
+            game: game.short_code(),
+            ordinal: mission.ordinal(),
+            mission_id: *mission.id().value(),
+            mission: mission.name(),
+            kind: mission.kind().into(),
+            par_time: mission.par_time().map(|time| time.format("%T")),
+            par_score: mission.par_score(),
+            halo_waypoint_id: missions_dao.find_halo_waypoint_id(game.id(), mission.id()),
+        }
+    }
+}
+
+impl Tabular for MissionRow {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "game",
+            "ordinal",
+            "mission_id",
+            "mission",
+            "kind",
+            "par_time",
+            "par_score",
+            "halo_waypoint_id",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let optional = |cell: Option<String>| cell.unwrap_or_else(|| "-".to_string());
+
+        vec![
+            self.game.clone(),
+            self.ordinal.to_string(),
+            self.mission_id.to_string(),
+            self.mission.clone(),
+            self.kind.to_string(),
+            optional(self.par_time.clone()),
+            optional(self.par_score.map(|score| score.to_string())),
+            optional(self.halo_waypoint_id.map(|id| id.to_string())),
+        ]
+    }
+}
@@ -0,0 +1,178 @@
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, Response, StatusCode};
+use std::cmp::Ordering;
+use std::io::Write;
+
+/// Content codings the server can produce, negotiated from `Accept-Encoding`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+    Identity,
+}
+
+impl Encoding {
+    /// Smaller bodies don't shrink enough to be worth the CPU.
+    const MIN_SIZE: usize = 1024;
+
+    /// Highest quality wins, brotli on ties; `*` stands for any coding not listed.
+    pub fn negotiate(accept_encoding: &str) -> Self {
+        let qualities = accept_encoding
+            .split(',')
+            .filter_map(|coding| {
+                let mut parts = coding.split(';');
+                let name = parts.next()?.trim().to_ascii_lowercase();
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                Some((name, quality))
+            })
+            .collect::<Vec<(String, f32)>>();
+        let quality = |name: &str| {
+            qualities
+                .iter()
+                .find(|(coding, _)| coding == name)
+                .or_else(|| qualities.iter().find(|(coding, _)| coding == "*"))
+                .map_or(0.0, |(_, quality)| *quality)
+        };
+
+        vec![(Self::Brotli, quality("br")), (Self::Gzip, quality("gzip"))]
+            .into_iter()
+            .filter(|(_, quality)| *quality > 0.0)
+            // max_by keeps the last of equal elements, so reverse to prefer brotli.
+            .rev()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map_or(Self::Identity, |(encoding, _)| encoding)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+            Self::Identity => "identity",
+        }
+    }
+
+    fn compressible(res: &Response<Body>) -> bool {
+        let content_type = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or_default();
+
+        !matches!(
+            res.status(),
+            StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED
+        ) && !res.headers().contains_key(header::CONTENT_ENCODING)
+            && (content_type.starts_with("text/")
+                || content_type.starts_with("application/json")
+                || content_type.starts_with("application/javascript"))
+    }
+
+    fn encode(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(vec![], 4096, 5, 22);
+                writer.write_all(body)?;
+                Ok(writer.into_inner())
+            }
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            Self::Identity => Ok(body.to_vec()),
+        }
+    }
+
+    /// Leaves small, binary or already encoded responses as they are.
+    pub async fn compress(self, res: Response<Body>) -> Result<Response<Body>, hyper::Error> {
+        if !Self::compressible(&res) {
+            return Ok(res);
+        }
+
+        let (mut parts, body) = res.into_parts();
+        parts
+            .headers
+            .append(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+        let body = hyper::body::to_bytes(body).await?;
+        if self == Self::Identity || body.len() < Self::MIN_SIZE {
+            return Ok(Response::from_parts(parts, Body::from(body)));
+        }
+
+        match self.encode(&body) {
+            Ok(encoded) => {
+                parts.headers.insert(
+                    header::CONTENT_ENCODING,
+                    HeaderValue::from_static(self.name()),
+                );
+                parts.headers.remove(header::CONTENT_LENGTH);
+                Ok(Response::from_parts(parts, Body::from(encoded)))
+            }
+            Err(_) => Ok(Response::from_parts(parts, Body::from(body))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod encoding_test {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn negotiate() {
+        assert_eq!(Encoding::negotiate("gzip, deflate, br"), Encoding::Brotli);
+        assert_eq!(Encoding::negotiate("gzip;q=1.0, br;q=0.5"), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate("br;q=0, gzip"), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate("*"), Encoding::Brotli);
+        assert_eq!(Encoding::negotiate("deflate"), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(""), Encoding::Identity);
+    }
+
+    fn json(size: usize) -> Response<Body> {
+        Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("[".to_string() + &"0,".repeat(size) + "0]"))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn compress_gzip() {
+        let res = Encoding::Gzip.compress(json(1000)).await.unwrap();
+
+        assert_eq!(res.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(res.headers()[header::VARY], "Accept-Encoding");
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "[".to_string() + &"0,".repeat(1000) + "0]");
+    }
+
+    #[tokio::test]
+    async fn compress_brotli() {
+        let res = Encoding::Brotli.compress(json(1000)).await.unwrap();
+
+        assert_eq!(res.headers()[header::CONTENT_ENCODING], "br");
+
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let mut decoded = String::new();
+        brotli::Decompressor::new(&body[..], 4096)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "[".to_string() + &"0,".repeat(1000) + "0]");
+    }
+
+    #[tokio::test]
+    async fn skip_small_bodies() {
+        let res = Encoding::Gzip.compress(json(10)).await.unwrap();
+
+        assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(res.headers()[header::VARY], "Accept-Encoding");
+    }
+}
@@ -0,0 +1,587 @@
+use hyper::header::HeaderName;
+use hyper::{Method, Uri};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::chainable::Chainable;
+use crate::error::{ConfigError, Error};
+use crate::halo_waypoint::endpoints::Endpoints;
+use crate::halo_waypoint::requests::auth::GetAuthRequest;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    port: u16,
+    shutdown_timeout_seconds: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 3000,
+            shutdown_timeout_seconds: 30,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_seconds)
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HaloWaypointConfig {
+    login: Option<String>,
+    password: Option<String>,
+    authorize_url: String,
+    client_id: String,
+    redirect_uri: String,
+    url: String,
+}
+
+impl Default for HaloWaypointConfig {
+    fn default() -> Self {
+        Self {
+            login: None,
+            password: None,
+            authorize_url: "https://login.live.com/oauth20_authorize.srf".to_string(),
+            client_id: "000000004C0BD2F1".to_string(),
+            redirect_uri: "https://www.halowaypoint.com/auth/callback".to_string(),
+            url: "https://www.halowaypoint.com".to_string(),
+        }
+    }
+}
+
+impl HaloWaypointConfig {
+    /// `None` until both the login and the password are set.
+    pub fn credentials(&self) -> Option<GetAuthRequest> {
+        match (&self.login, &self.password) {
+            (Some(login), Some(password)) => Some(GetAuthRequest::new(login, password)),
+            _ => None,
+        }
+    }
+
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints::new(
+            &self.authorize_url,
+            &self.client_id,
+            &self.redirect_uri,
+            &self.url,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    auth_capacity: usize,
+    auth_ttl_seconds: u64,
+    service_record_capacity: usize,
+    service_record_ttl_seconds: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            auth_capacity: 10,
+            auth_ttl_seconds: 14400,
+            service_record_capacity: 1000,
+            service_record_ttl_seconds: 600,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn auth_capacity(&self) -> usize {
+        self.auth_capacity
+    }
+
+    pub fn auth_ttl(&self) -> Duration {
+        Duration::from_secs(self.auth_ttl_seconds)
+    }
+
+    pub fn service_record_capacity(&self) -> usize {
+        self.service_record_capacity
+    }
+
+    pub fn service_record_ttl(&self) -> Duration {
+        Duration::from_secs(self.service_record_ttl_seconds)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogueConfig {
+    path: Option<PathBuf>,
+    reload: bool,
+    world_records_path: Option<PathBuf>,
+}
+
+impl CatalogueConfig {
+    /// `None` serves the catalogue shipped in the binary.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.path.clone()
+    }
+
+    pub fn reload(&self) -> bool {
+        self.reload
+    }
+
+    /// `None` serves the world records shipped in the binary.
+    pub fn world_records_path(&self) -> Option<PathBuf> {
+        self.world_records_path.clone()
+    }
+}
+
+/// Cross-origin access for browser clients, disabled while `allowed_origins` is empty.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    allow_credentials: bool,
+    max_age_seconds: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![],
+            allowed_methods: vec!["GET".to_string(), "POST".to_string(), "OPTIONS".to_string()],
+            allowed_headers: vec!["Content-Type".to_string(), "Accept-Language".to_string()],
+            allow_credentials: false,
+            max_age_seconds: 600,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// `*` allows any origin.
+    pub fn allowed_origins(&self) -> &[String] {
+        &self.allowed_origins
+    }
+
+    pub fn allowed_methods(&self) -> &[String] {
+        &self.allowed_methods
+    }
+
+    pub fn allowed_headers(&self) -> &[String] {
+        &self.allowed_headers
+    }
+
+    pub fn allow_credentials(&self) -> bool {
+        self.allow_credentials
+    }
+
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age_seconds)
+    }
+}
+
+/// Settings read from the `CONFIG_PATH` TOML file, each overridable by an environment variable.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    server: ServerConfig,
+    halo_waypoint: HaloWaypointConfig,
+    cache: CacheConfig,
+    catalogue: CatalogueConfig,
+    cors: CorsConfig,
+}
+
+impl Config {
+    pub fn server(&self) -> &ServerConfig {
+        &self.server
+    }
+
+    pub fn halo_waypoint(&self) -> &HaloWaypointConfig {
+        &self.halo_waypoint
+    }
+
+    pub fn cache(&self) -> &CacheConfig {
+        &self.cache
+    }
+
+    pub fn catalogue(&self) -> &CatalogueConfig {
+        &self.catalogue
+    }
+
+    pub fn cors(&self) -> &CorsConfig {
+        &self.cors
+    }
+
+    pub fn load() -> Result<Self, Error> {
+        let file = match env::var("CONFIG_PATH") {
+            Ok(path) => fs::read_to_string(&path)
+                .map_err(|err| ConfigError::Io(path, err.to_string()).pipe(Error::Config))?
+                .pipe(Some),
+            Err(_) => None,
+        };
+
+        Self::try_from_sources(file.as_deref(), &env::vars().collect())
+    }
+
+    pub fn try_from_sources(
+        file: Option<&str>,
+        env: &HashMap<String, String>,
+    ) -> Result<Self, Error> {
+        let mut config: Self = match file {
+            Some(file) => toml::from_str(file)
+                .map_err(|err| ConfigError::Parse(err.to_string()).pipe(Error::Config))?,
+            None => Self::default(),
+        };
+
+        config.apply_env(env).map_err(Error::Config)?;
+
+        let errors = config.validate();
+        if !errors.is_empty() {
+            return errors
+                .into_iter()
+                .map(Error::Config)
+                .collect::<Vec<Error>>()
+                .pipe(Error::List)
+                .pipe(Err);
+        }
+
+        Ok(config)
+    }
+
+    fn var<T: FromStr>(env: &HashMap<String, String>, key: &str) -> Result<Option<T>, ConfigError> {
+        env.get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidVariable(key.to_string(), value.to_string()))
+            })
+            .transpose()
+    }
+
+    fn apply_env(&mut self, env: &HashMap<String, String>) -> Result<(), ConfigError> {
+        macro_rules! override_with {
+            ($field:expr, $key:expr) => {
+                if let Some(value) = Self::var(env, $key)? {
+                    $field = value;
+                }
+            };
+            (Some $field:expr, $key:expr) => {
+                if let Some(value) = Self::var(env, $key)? {
+                    $field = Some(value);
+                }
+            };
+            (List $field:expr, $key:expr) => {
+                if let Some(value) = env.get($key) {
+                    $field = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+            };
+        }
+
+        override_with!(self.server.port, "PORT");
+        override_with!(self.server.shutdown_timeout_seconds, "SHUTDOWN_TIMEOUT");
+
+        override_with!(Some self.halo_waypoint.login, "HALO_WAYPOINT_LOGIN");
+        override_with!(Some self.halo_waypoint.password, "HALO_WAYPOINT_PASSWORD");
+        override_with!(
+            self.halo_waypoint.authorize_url,
+            "HALO_WAYPOINT_AUTHORIZE_URL"
+        );
+        override_with!(self.halo_waypoint.client_id, "HALO_WAYPOINT_CLIENT_ID");
+        override_with!(
+            self.halo_waypoint.redirect_uri,
+            "HALO_WAYPOINT_REDIRECT_URI"
+        );
+        override_with!(self.halo_waypoint.url, "HALO_WAYPOINT_URL");
+
+        override_with!(self.cache.auth_capacity, "AUTH_CACHE_CAPACITY");
+        override_with!(self.cache.auth_ttl_seconds, "AUTH_CACHE_TTL");
+        override_with!(
+            self.cache.service_record_capacity,
+            "SERVICE_RECORD_CACHE_CAPACITY"
+        );
+        override_with!(
+            self.cache.service_record_ttl_seconds,
+            "SERVICE_RECORD_CACHE_TTL"
+        );
+
+        override_with!(Some self.catalogue.path, "CATALOGUE_PATH");
+        override_with!(self.catalogue.reload, "CATALOGUE_RELOAD");
+        override_with!(Some self.catalogue.world_records_path, "WORLD_RECORDS_PATH");
+
+        override_with!(List self.cors.allowed_origins, "CORS_ALLOWED_ORIGINS");
+        override_with!(List self.cors.allowed_methods, "CORS_ALLOWED_METHODS");
+        override_with!(List self.cors.allowed_headers, "CORS_ALLOWED_HEADERS");
+        override_with!(self.cors.allow_credentials, "CORS_ALLOW_CREDENTIALS");
+        override_with!(self.cors.max_age_seconds, "CORS_MAX_AGE");
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Vec<ConfigError> {
+        let positive = vec![
+            ("cache.auth_capacity", self.cache.auth_capacity as u64),
+            ("cache.auth_ttl_seconds", self.cache.auth_ttl_seconds),
+            (
+                "cache.service_record_capacity",
+                self.cache.service_record_capacity as u64,
+            ),
+            (
+                "cache.service_record_ttl_seconds",
+                self.cache.service_record_ttl_seconds,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value == 0)
+        .map(|(key, _)| ConfigError::Invalid(key.to_string(), "must be positive".to_string()));
+
+        let urls = vec![
+            (
+                "halo_waypoint.authorize_url",
+                &self.halo_waypoint.authorize_url,
+            ),
+            (
+                "halo_waypoint.redirect_uri",
+                &self.halo_waypoint.redirect_uri,
+            ),
+            ("halo_waypoint.url", &self.halo_waypoint.url),
+        ]
+        .into_iter()
+        // Parsed now, requests built from them later unwrap their URIs.
+        .filter(|(_, url)| {
+            !matches!(
+                url.parse::<Uri>(),
+                Ok(uri) if matches!(uri.scheme_str(), Some("http") | Some("https"))
+                    && uri.authority().is_some()
+            )
+        })
+        .map(|(key, _)| {
+            ConfigError::Invalid(key.to_string(), "must be an http(s) URL".to_string())
+        });
+
+        let credentials = match (&self.halo_waypoint.login, &self.halo_waypoint.password) {
+            (Some(_), None) | (None, Some(_)) => Some(ConfigError::Invalid(
+                "halo_waypoint".to_string(),
+                "login and password must be set together".to_string(),
+            )),
+            _ => None,
+        };
+
+        let methods = self
+            .cors
+            .allowed_methods
+            .iter()
+            .filter(|method| Method::from_bytes(method.as_bytes()).is_err())
+            .map(|method| {
+                ConfigError::Invalid(
+                    "cors.allowed_methods".to_string(),
+                    format!("{} is not an HTTP method", method),
+                )
+            });
+
+        let headers = self
+            .cors
+            .allowed_headers
+            .iter()
+            .filter(|header| HeaderName::from_bytes(header.as_bytes()).is_err())
+            .map(|header| {
+                ConfigError::Invalid(
+                    "cors.allowed_headers".to_string(),
+                    format!("{} is not an HTTP header name", header),
+                )
+            });
+
+        // Browsers reject credentialed responses allowing any origin.
+        let cors = if self.cors.allow_credentials
+            && self.cors.allowed_origins.iter().any(|origin| origin == "*")
+        {
+            Some(ConfigError::Invalid(
+                "cors".to_string(),
+                "credentials can't be allowed for any origin".to_string(),
+            ))
+        } else {
+            None
+        };
+
+        positive
+            .chain(urls)
+            .chain(credentials)
+            .chain(methods)
+            .chain(headers)
+            .chain(cors)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod config_test {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults() {
+        let config = Config::try_from_sources(None, &env(&[])).unwrap();
+
+        assert_eq!(config.server().port(), 3000);
+        assert_eq!(config.cache().auth_capacity(), 10);
+        assert_eq!(config.cache().auth_ttl(), Duration::from_secs(14400));
+        assert_eq!(config.cache().service_record_capacity(), 1000);
+        assert_eq!(
+            config.cache().service_record_ttl(),
+            Duration::from_secs(600)
+        );
+        assert!(config.halo_waypoint().credentials().is_none());
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let config = Config::try_from_sources(
+            Some(
+                r#"
+                [server]
+                port = 8080
+
+                [cache]
+                service_record_ttl_seconds = 60
+                "#,
+            ),
+            &env(&[
+                ("PORT", "9090"),
+                ("HALO_WAYPOINT_LOGIN", "john"),
+                ("HALO_WAYPOINT_PASSWORD", "117"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.server().port(), 9090);
+        assert_eq!(config.cache().service_record_ttl(), Duration::from_secs(60));
+        assert!(config.halo_waypoint().credentials().is_some());
+    }
+
+    #[test]
+    fn cors_lists_from_env() {
+        let config = Config::try_from_sources(
+            None,
+            &env(&[
+                (
+                    "CORS_ALLOWED_ORIGINS",
+                    "https://halo.example, https://mcc.example",
+                ),
+                ("CORS_MAX_AGE", "60"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.cors().allowed_origins(),
+            ["https://halo.example", "https://mcc.example"]
+        );
+        assert_eq!(config.cors().allowed_methods(), ["GET", "POST", "OPTIONS"]);
+        assert_eq!(config.cors().max_age(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn cors_credentials_for_any_origin() {
+        let res = Config::try_from_sources(
+            None,
+            &env(&[
+                ("CORS_ALLOWED_ORIGINS", "*"),
+                ("CORS_ALLOW_CREDENTIALS", "true"),
+            ]),
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Config(ConfigError::Invalid(key, _)),
+            ] if key == "cors")
+        ));
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let res = Config::try_from_sources(
+            Some(
+                r#"
+                [cache]
+                service_record_tll_seconds = 60
+                "#,
+            ),
+            &env(&[]),
+        );
+
+        assert!(
+            matches!(res, Err(Error::Config(ConfigError::Parse(err))) if err.contains("unknown field"))
+        );
+    }
+
+    #[test]
+    fn invalid_urls() {
+        let res = Config::try_from_sources(
+            None,
+            &env(&[
+                ("HALO_WAYPOINT_URL", "https://halo waypoint.com"),
+                ("HALO_WAYPOINT_REDIRECT_URI", "/auth/callback"),
+            ]),
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Config(ConfigError::Invalid(redirect_uri, _)),
+                Error::Config(ConfigError::Invalid(url, _)),
+            ] if redirect_uri == "halo_waypoint.redirect_uri" && url == "halo_waypoint.url")
+        ));
+    }
+
+    #[test]
+    fn invalid_variable() {
+        assert!(matches!(
+            Config::try_from_sources(None, &env(&[("PORT", "http")])),
+            Err(Error::Config(ConfigError::InvalidVariable(key, _))) if key == "PORT"
+        ));
+    }
+
+    #[test]
+    fn invalid_values() {
+        let res = Config::try_from_sources(
+            Some(
+                r#"
+                [cache]
+                auth_capacity = 0
+
+                [halo_waypoint]
+                login = "john"
+                "#,
+            ),
+            &env(&[]),
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Config(ConfigError::Invalid(_, _)),
+                Error::Config(ConfigError::Invalid(_, _)),
+            ])
+        ));
+    }
+}
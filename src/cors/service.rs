@@ -0,0 +1,309 @@
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, Request, Response, StatusCode};
+
+use crate::configs::config::CorsConfig;
+
+/// Answers preflight requests and adds the CORS headers to every other response.
+#[derive(Clone)]
+pub struct Service {
+    config: CorsConfig,
+}
+
+impl Service {
+    pub fn new(config: &CorsConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// Disallowed preflights get a 403 without CORS headers, so the browser blocks the request.
+    pub async fn preflight(
+        &self,
+        req: Request<Body>,
+    ) -> Result<Response<Body>, hyper::error::Error> {
+        let origin = req.headers().get(header::ORIGIN);
+        let method = req
+            .headers()
+            .get(header::ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|method| method.to_str().ok());
+
+        let (origin, method) = match (origin, method) {
+            (Some(origin), Some(method)) => (origin, method),
+            // A plain OPTIONS request rather than a preflight.
+            _ => {
+                return Ok(Response::builder()
+                    .status(StatusCode::NO_CONTENT)
+                    .header(header::ALLOW, self.config.allowed_methods().join(", "))
+                    .body(Body::empty())
+                    .unwrap())
+            }
+        };
+
+        let headers = req
+            .headers()
+            .get(header::ACCESS_CONTROL_REQUEST_HEADERS)
+            .and_then(|headers| headers.to_str().ok())
+            .unwrap_or_default();
+
+        if !self.allows_origin(origin)
+            || !self.allows_method(method)
+            || !self.allows_headers(headers)
+        {
+            return Ok(Response::builder()
+                .status(StatusCode::FORBIDDEN)
+                .body(Body::empty())
+                .unwrap());
+        }
+
+        let mut res = Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .header(
+                header::ACCESS_CONTROL_ALLOW_METHODS,
+                self.config.allowed_methods().join(", "),
+            )
+            .header(
+                header::ACCESS_CONTROL_ALLOW_HEADERS,
+                self.config.allowed_headers().join(", "),
+            )
+            .header(
+                header::ACCESS_CONTROL_MAX_AGE,
+                self.config.max_age().as_secs(),
+            )
+            .body(Body::empty())
+            .unwrap();
+        self.apply(Some(origin), &mut res);
+
+        Ok(res)
+    }
+
+    /// Leaves the CORS headers out when the origin isn't allowed or they are already set, as on
+    /// preflight answers.
+    ///
+    /// With a list of origins the response depends on the request's `Origin`, even when it has
+    /// none or a disallowed one, so caches are always told to vary on it.
+    pub fn apply(&self, origin: Option<&HeaderValue>, res: &mut Response<Body>) {
+        if self.echoes_origin() && !Self::varies_on_origin(res) {
+            res.headers_mut()
+                .append(header::VARY, HeaderValue::from_static("Origin"));
+        }
+
+        let origin = match origin {
+            Some(origin)
+                if self.allows_origin(origin)
+                    && !res
+                        .headers()
+                        .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN) =>
+            {
+                origin
+            }
+            _ => return,
+        };
+
+        let headers = res.headers_mut();
+        if self.echoes_origin() {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        } else {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_ORIGIN,
+                HeaderValue::from_static("*"),
+            );
+        }
+
+        if self.config.allow_credentials() {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+    }
+
+    /// A wildcard is only sent without credentials, otherwise the allowed origin is echoed.
+    fn echoes_origin(&self) -> bool {
+        !self.config.allowed_origins().is_empty()
+            && (!self.allows_any_origin() || self.config.allow_credentials())
+    }
+
+    fn varies_on_origin(res: &Response<Body>) -> bool {
+        res.headers()
+            .get_all(header::VARY)
+            .iter()
+            .filter_map(|vary| vary.to_str().ok())
+            .flat_map(|vary| vary.split(','))
+            .any(|vary| vary.trim().eq_ignore_ascii_case("origin"))
+    }
+
+    fn allows_any_origin(&self) -> bool {
+        self.config
+            .allowed_origins()
+            .iter()
+            .any(|allowed| allowed == "*")
+    }
+
+    fn allows_origin(&self, origin: &HeaderValue) -> bool {
+        self.allows_any_origin()
+            || self
+                .config
+                .allowed_origins()
+                .iter()
+                .any(|allowed| allowed.as_bytes() == origin.as_bytes())
+    }
+
+    fn allows_method(&self, method: &str) -> bool {
+        self.config
+            .allowed_methods()
+            .iter()
+            .any(|allowed| allowed == method)
+    }
+
+    /// Header names are case-insensitive; an empty request list is always allowed.
+    fn allows_headers(&self, headers: &str) -> bool {
+        headers
+            .split(',')
+            .map(str::trim)
+            .filter(|header| !header.is_empty())
+            .all(|header| {
+                self.config
+                    .allowed_headers()
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(header))
+            })
+    }
+}
+
+#[cfg(test)]
+mod service_test {
+    use super::*;
+    use crate::configs::config::Config;
+    use hyper::Method;
+    use std::collections::HashMap;
+
+    fn service(cors: &str) -> Service {
+        let config = Config::try_from_sources(Some(cors), &HashMap::new()).unwrap();
+
+        Service::new(config.cors())
+    }
+
+    fn preflight(origin: &str, method: &str, headers: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/graphql")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, headers)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn preflight_allowed() {
+        let service = service(
+            r#"
+            [cors]
+            allowed_origins = ["https://halo.example"]
+            allow_credentials = true
+            "#,
+        );
+
+        let res = service
+            .preflight(preflight("https://halo.example", "POST", "content-type"))
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            res.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://halo.example"
+        );
+        assert_eq!(
+            res.headers()[header::ACCESS_CONTROL_ALLOW_CREDENTIALS],
+            "true"
+        );
+        assert_eq!(res.headers()[header::ACCESS_CONTROL_MAX_AGE], "600");
+        assert_eq!(res.headers()[header::VARY], "Origin");
+    }
+
+    #[tokio::test]
+    async fn preflight_forbidden() {
+        let service = service(
+            r#"
+            [cors]
+            allowed_origins = ["https://halo.example"]
+            "#,
+        );
+
+        let origin = service
+            .preflight(preflight("https://evil.example", "POST", ""))
+            .await
+            .unwrap();
+        let method = service
+            .preflight(preflight("https://halo.example", "DELETE", ""))
+            .await
+            .unwrap();
+        let headers = service
+            .preflight(preflight("https://halo.example", "POST", "Authorization"))
+            .await
+            .unwrap();
+
+        assert_eq!(origin.status(), StatusCode::FORBIDDEN);
+        assert_eq!(method.status(), StatusCode::FORBIDDEN);
+        assert_eq!(headers.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn apply_any_origin() {
+        let service = service(
+            r#"
+            [cors]
+            allowed_origins = ["*"]
+            "#,
+        );
+        let mut res = Response::new(Body::empty());
+
+        service.apply(
+            Some(&HeaderValue::from_static("https://halo.example")),
+            &mut res,
+        );
+
+        assert_eq!(res.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert!(res.headers().get(header::VARY).is_none());
+    }
+
+    #[test]
+    fn apply_disabled() {
+        let service = service("");
+        let mut res = Response::new(Body::empty());
+
+        service.apply(
+            Some(&HeaderValue::from_static("https://halo.example")),
+            &mut res,
+        );
+
+        assert!(res.headers().is_empty());
+    }
+
+    #[test]
+    fn apply_varies_on_origin_without_allowed_origin() {
+        let service = service(
+            r#"
+            [cors]
+            allowed_origins = ["https://halo.example"]
+            "#,
+        );
+        let mut missing = Response::new(Body::empty());
+        let mut disallowed = Response::new(Body::empty());
+
+        service.apply(None, &mut missing);
+        service.apply(
+            Some(&HeaderValue::from_static("https://evil.example")),
+            &mut disallowed,
+        );
+
+        for res in vec![missing, disallowed] {
+            assert_eq!(res.headers()[header::VARY], "Origin");
+            assert!(res
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none());
+        }
+    }
+}
@@ -1,7 +1,22 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use strum_macros::IntoStaticStr;
+
+/// Ordered from easiest to hardest: a Legendary clear also counts for Heroic and below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
 pub enum Difficulty {
     Easy,
     Normal,
     Heroic,
     Legendary,
 }
+
+#[cfg(test)]
+mod difficulty_test {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        assert!(Difficulty::Easy < Difficulty::Normal);
+        assert!(Difficulty::Heroic < Difficulty::Legendary);
+    }
+}
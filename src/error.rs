@@ -1,6 +1,13 @@
+use std::fmt;
+use strum_macros::IntoStaticStr;
+
 #[derive(Debug, Clone)]
 pub enum Error {
+    Catalogue(CatalogueError),
+    Config(ConfigError),
     Hyper(String),
+    InvalidArgument(String),
+    InvalidGamertag(String),
     HaloWaypoint(HaloWaypointError),
     List(Vec<Error>),
 }
@@ -11,13 +18,54 @@ impl Error {
     }
 }
 
-#[derive(Debug, Clone)]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Catalogue(err) => write!(f, "Invalid catalogue: {:?}", err),
+            Self::Config(err) => write!(f, "Invalid configuration: {}", err),
+            Self::Hyper(_) => write!(f, "Halo Waypoint is unreachable"),
+            Self::InvalidArgument(reason) => write!(f, "Invalid argument: {}", reason),
+            Self::InvalidGamertag(gamertag) => write!(f, "Invalid gamertag: {}", gamertag),
+            Self::HaloWaypoint(HaloWaypointError::PlayerNotFound) => write!(f, "Player not found"),
+            Self::HaloWaypoint(HaloWaypointError::PrivateServiceRecord) => {
+                write!(f, "Player service record is private")
+            }
+            Self::HaloWaypoint(HaloWaypointError::UnknownMissionId(id)) => {
+                write!(f, "Halo Waypoint returned unknown mission {}", id)
+            }
+            Self::HaloWaypoint(HaloWaypointError::MissionTitleMismatch {
+                game_id,
+                mission_id,
+                actual,
+                ..
+            }) => write!(
+                f,
+                "Halo Waypoint titled mission {} of game {} {:?}",
+                mission_id, game_id, actual
+            ),
+            Self::HaloWaypoint(_) | Self::List(_) => {
+                write!(f, "Halo Waypoint returned an unexpected response")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, IntoStaticStr)]
 pub enum HaloWaypointError {
     Http(/* http::response::Parts, */ String),
+    PlayerNotFound,
+    PrivateServiceRecord,
     MissingGame,
     UnknownGame(String),
     MissingMissionId,
     InvalidMissionId(String),
+    UnknownMissionId(i32),
+    MissionTitleMismatch {
+        game_id: i32,
+        mission_id: i32,
+        expected: Option<String>,
+        actual: String,
+    },
     MissingDifficulty,
     UnknownDifficulty(String),
     MissingCampaignMode,
@@ -27,3 +75,69 @@ pub enum HaloWaypointError {
     MissingScore,
     InvalidScore(String),
 }
+
+impl HaloWaypointError {
+    /// A page came back but didn't have the expected shape.
+    pub fn is_parse_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingGame
+                | Self::UnknownGame(_)
+                | Self::MissingMissionId
+                | Self::InvalidMissionId(_)
+                | Self::UnknownMissionId(_)
+                | Self::MissingDifficulty
+                | Self::UnknownDifficulty(_)
+                | Self::MissingCampaignMode
+                | Self::UnknownCampaignMode(_)
+                | Self::MissingTime
+                | Self::InvalidTime(_)
+                | Self::MissingScore
+                | Self::InvalidScore(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CatalogueError {
+    Io(String),
+    Parse(String),
+    DuplicateGameId(i32),
+    DuplicateGameShortCode(String),
+    DuplicateMissionId(i32, i32),
+    DuplicateMissionOrdinal(i32, i32),
+    DuplicateHaloWaypointId(i32, i32),
+    MissingGame(i32),
+    MissingMission(i32, i32),
+    InvalidParTime(String),
+    DuplicateParVersion(String),
+    UnknownParVersion(String),
+    InvalidEffectiveFrom(String),
+    InvalidWorldRecord(String),
+    DuplicateWorldRecord(i32, i32),
+    UnknownMissionKind(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    Io(String, String),
+    Parse(String),
+    InvalidVariable(String, String),
+    Invalid(String, String),
+    MissingCredentials,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "cannot read {}: {}", path, err),
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::InvalidVariable(key, value) => write!(f, "{}={} cannot be parsed", key, value),
+            Self::Invalid(key, reason) => write!(f, "{} {}", key, reason),
+            Self::MissingCredentials => write!(
+                f,
+                "HALO_WAYPOINT_LOGIN and HALO_WAYPOINT_PASSWORD are not set"
+            ),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::hash::{Hash, Hasher};
+
+use crate::chainable::Chainable;
+use crate::error::Error;
+
+/// Xbox gamertag, compared and hashed case-insensitively but displayed as entered.
+#[derive(Clone, Debug)]
+pub struct Gamertag(String);
+
+impl Gamertag {
+    const MAX_LENGTH: usize = 15;
+
+    /// Gamertags are 1 to 15 letters, digits or single spaces, starting with a letter.
+    pub fn try_from_str(gamertag: &str) -> Result<Self, Error> {
+        let valid = gamertag.len() <= Self::MAX_LENGTH
+            && gamertag
+                .chars()
+                .next()
+                .map_or(false, |c| c.is_ascii_alphabetic())
+            && !gamertag.ends_with(' ')
+            && !gamertag.contains("  ")
+            && gamertag
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == ' ');
+
+        if valid {
+            Ok(Self(gamertag.to_string()))
+        } else {
+            gamertag.to_string().pipe(Error::InvalidGamertag).pipe(Err)
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    fn key(&self) -> String {
+        self.0.to_ascii_lowercase()
+    }
+}
+
+impl PartialEq for Gamertag {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Gamertag {}
+
+impl Hash for Gamertag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod gamertag_test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn try_from_str_valid() {
+        assert!(Gamertag::try_from_str("John117").is_ok());
+        assert!(Gamertag::try_from_str("Master Chief").is_ok());
+        assert!(Gamertag::try_from_str("A").is_ok());
+        assert!(Gamertag::try_from_str("Abcdefghijklmno").is_ok());
+    }
+
+    #[test]
+    fn try_from_str_invalid() {
+        assert!(Gamertag::try_from_str("").is_err());
+        assert!(Gamertag::try_from_str("117John").is_err());
+        assert!(Gamertag::try_from_str(" John").is_err());
+        assert!(Gamertag::try_from_str("John ").is_err());
+        assert!(Gamertag::try_from_str("Master  Chief").is_err());
+        assert!(Gamertag::try_from_str("John/117").is_err());
+        assert!(Gamertag::try_from_str("Abcdefghijklmnop").is_err());
+    }
+
+    #[test]
+    fn case_insensitive_identity() {
+        let upper = Gamertag::try_from_str("JOHN117").unwrap();
+        let lower = Gamertag::try_from_str("john117").unwrap();
+
+        assert_eq!(upper, lower);
+        assert_eq!(
+            vec![upper.clone(), lower]
+                .into_iter()
+                .collect::<HashSet<_>>()
+                .len(),
+            1
+        );
+        assert_eq!(upper.value(), "JOHN117");
+    }
+}
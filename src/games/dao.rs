@@ -1,26 +1,23 @@
+use std::sync::Arc;
+
+use crate::catalogues::file_catalogue::FileCatalogue;
 use crate::games::game::{Game, GameId};
 
 pub trait GamesDao {
     fn all(&self) -> Vec<Game>;
     fn find_by_id(&self, id: GameId) -> Option<Game>;
+    fn find_by_short_code(&self, short_code: &str) -> Option<Game>;
+    fn find_by_halo_waypoint_key(&self, halo_waypoint_key: &str) -> Option<Game>;
 }
 
+#[derive(Clone)]
 pub struct InMemoryGamesDao {
     games: Vec<Game>,
 }
 
 impl InMemoryGamesDao {
-    pub fn default() -> Self {
-        Self {
-            games: vec![
-                Game::new(GameId::new(1), "Halo: Combat Evolved Anniversary"),
-                Game::new(GameId::new(2), "Halo 2: Anniversary"),
-                Game::new(GameId::new(3), "Halo 3"),
-                Game::new(GameId::new(4), "Halo 3: ODST"),
-                Game::new(GameId::new(5), "Halo: Reach"),
-                Game::new(GameId::new(6), "Halo 4"),
-            ],
-        }
+    pub fn new(games: Vec<Game>) -> Self {
+        Self { games }
     }
 }
 
@@ -32,4 +29,52 @@ impl GamesDao for InMemoryGamesDao {
     fn find_by_id(&self, id: GameId) -> Option<Game> {
         self.games.iter().find(|game| game.id() == id).cloned()
     }
+
+    fn find_by_short_code(&self, short_code: &str) -> Option<Game> {
+        self.games
+            .iter()
+            .find(|game| game.short_code().eq_ignore_ascii_case(short_code))
+            .cloned()
+    }
+
+    fn find_by_halo_waypoint_key(&self, halo_waypoint_key: &str) -> Option<Game> {
+        self.games
+            .iter()
+            .find(|game| game.halo_waypoint_key() == halo_waypoint_key)
+            .cloned()
+    }
+}
+
+pub struct FileGamesDao {
+    catalogue: Arc<FileCatalogue>,
+}
+
+impl FileGamesDao {
+    pub fn new(catalogue: Arc<FileCatalogue>) -> Self {
+        Self { catalogue }
+    }
+}
+
+impl GamesDao for FileGamesDao {
+    fn all(&self) -> Vec<Game> {
+        self.catalogue.current().games_dao().all()
+    }
+
+    fn find_by_id(&self, id: GameId) -> Option<Game> {
+        self.catalogue.current().games_dao().find_by_id(id)
+    }
+
+    fn find_by_short_code(&self, short_code: &str) -> Option<Game> {
+        self.catalogue
+            .current()
+            .games_dao()
+            .find_by_short_code(short_code)
+    }
+
+    fn find_by_halo_waypoint_key(&self, halo_waypoint_key: &str) -> Option<Game> {
+        self.catalogue
+            .current()
+            .games_dao()
+            .find_by_halo_waypoint_key(halo_waypoint_key)
+    }
 }
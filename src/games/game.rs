@@ -29,13 +29,35 @@ impl PartialOrd for GameId {
 pub struct Game {
     id: GameId,
     name: String,
+    short_code: String,
+    release_year: i32,
+    developer: String,
+    halo_waypoint_key: String,
+    mission_count: i32,
+    menu_order: i32,
 }
 
 impl Game {
-    pub fn new(id: GameId, name: &str) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: GameId,
+        name: &str,
+        short_code: &str,
+        release_year: i32,
+        developer: &str,
+        halo_waypoint_key: &str,
+        mission_count: i32,
+        menu_order: i32,
+    ) -> Self {
         Self {
             id,
             name: name.to_string(),
+            short_code: short_code.to_string(),
+            release_year,
+            developer: developer.to_string(),
+            halo_waypoint_key: halo_waypoint_key.to_string(),
+            mission_count,
+            menu_order,
         }
     }
 
@@ -46,4 +68,28 @@ impl Game {
     pub fn name(&self) -> String {
         self.name.clone()
     }
+
+    pub fn short_code(&self) -> String {
+        self.short_code.clone()
+    }
+
+    pub fn release_year(&self) -> i32 {
+        self.release_year
+    }
+
+    pub fn developer(&self) -> String {
+        self.developer.clone()
+    }
+
+    pub fn halo_waypoint_key(&self) -> String {
+        self.halo_waypoint_key.clone()
+    }
+
+    pub fn mission_count(&self) -> i32 {
+        self.mission_count
+    }
+
+    pub fn menu_order(&self) -> i32 {
+        self.menu_order
+    }
 }
@@ -1,11 +1,32 @@
-use crate::games::dao::{GamesDao, InMemoryGamesDao};
-use crate::missions::dao::{InMemoryMissionsDao, MissionsDao};
+use itertools::Itertools;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::catalogues::catalogue::Catalogue;
+use crate::catalogues::file_catalogue::FileCatalogue;
+use crate::chainable::Chainable;
+use crate::configs::config::{CatalogueConfig, Config};
+use crate::error::{CatalogueError, Error};
+use crate::games::dao::{FileGamesDao, GamesDao};
+use crate::halo_waypoint::client::{HyperClient, InMemoryCacheClient};
+use crate::halo_waypoint::freshness::Freshness;
+use crate::locales::locale::Locale;
+use crate::localisations::dao::{InMemoryLocalisationsDao, LocalisationsDao};
+use crate::missions::dao::{FileMissionsDao, MissionsDao};
 use crate::service_records::dao::ServiceRecordsDao;
+use crate::service_records::fetched::Fetched;
+use crate::world_records::dao::{InMemoryWorldRecordsDao, WorldRecordsDao};
 
+#[derive(Clone)]
 pub struct Context {
-    games_dao: Box<dyn GamesDao + Send + Sync>,
-    missions_dao: Box<dyn MissionsDao + Send + Sync>,
-    service_records_doa: ServiceRecordsDao,
+    games_dao: Arc<dyn GamesDao + Send + Sync>,
+    missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+    localisations_dao: Arc<dyn LocalisationsDao + Send + Sync>,
+    service_records_doa: Arc<ServiceRecordsDao>,
+    world_records_dao: Arc<dyn WorldRecordsDao + Send + Sync>,
+    accept_language: Vec<Locale>,
+    freshness: Arc<Mutex<Vec<Option<Freshness>>>>,
+    warnings: Arc<Mutex<Vec<Error>>>,
 }
 
 impl Context {
@@ -17,17 +38,142 @@ impl Context {
         &*self.missions_dao
     }
 
+    pub fn localisations_dao(&self) -> &(dyn LocalisationsDao + Send + Sync) {
+        &*self.localisations_dao
+    }
+
     pub fn service_records_doa(&self) -> &ServiceRecordsDao {
         &self.service_records_doa
     }
 
-    pub fn default() -> Self {
+    pub fn world_records_dao(&self) -> &(dyn WorldRecordsDao + Send + Sync) {
+        &*self.world_records_dao
+    }
+
+    /// The requested locale, otherwise the request's `Accept-Language` preferences.
+    pub fn locales(&self, locale: Option<Locale>) -> Vec<Locale> {
+        match locale {
+            Some(locale) => vec![locale],
+            None => self.accept_language.clone(),
+        }
+    }
+
+    /// Shares the DAOs with a context scoped to one request.
+    pub fn for_request(&self, accept_language: Vec<Locale>) -> Self {
         Self {
-            games_dao: Box::new(InMemoryGamesDao::default()),
-            missions_dao: Box::new(InMemoryMissionsDao::default()),
-            service_records_doa: ServiceRecordsDao::default(),
+            accept_language,
+            freshness: Arc::new(Mutex::new(vec![])),
+            warnings: Arc::new(Mutex::new(vec![])),
+            ..self.clone()
         }
     }
+
+    /// Notes how fresh the service records the request read are, for its caching headers, and
+    /// their inconsistencies, which the response lists.
+    pub fn record<T>(&self, fetched: Fetched<T>) -> T {
+        self.freshness.lock().unwrap().push(fetched.freshness());
+        self.warnings
+            .lock()
+            .unwrap()
+            .extend(fetched.inconsistencies().iter().cloned());
+
+        fetched.into_records()
+    }
+
+    /// `None` when no service record was read, `Some(None)` when one of them isn't cached.
+    pub fn freshness(&self) -> Option<Option<Freshness>> {
+        let freshness = self.freshness.lock().unwrap();
+        if freshness.is_empty() {
+            return None;
+        }
+
+        freshness
+            .iter()
+            .copied()
+            .collect::<Option<Vec<Freshness>>>()
+            .and_then(|freshness| freshness.into_iter().fold1(Freshness::stalest))
+            .pipe(Some)
+    }
+
+    pub fn warnings(&self) -> Vec<Error> {
+        self.warnings.lock().unwrap().clone()
+    }
+
+    /// Serves the configured catalogue file, otherwise the catalogue shipped in the binary.
+    #[allow(clippy::type_complexity)]
+    fn catalogue_daos(
+        config: &CatalogueConfig,
+    ) -> Result<
+        (
+            Arc<dyn GamesDao + Send + Sync>,
+            Arc<dyn MissionsDao + Send + Sync>,
+        ),
+        Error,
+    > {
+        match config.path() {
+            Some(path) => {
+                let catalogue =
+                    FileCatalogue::try_from_path(&path, config.reload()).map(Arc::new)?;
+
+                Ok((
+                    Arc::new(FileGamesDao::new(catalogue.clone())),
+                    Arc::new(FileMissionsDao::new(catalogue)),
+                ))
+            }
+            None => {
+                let catalogue = Catalogue::default();
+
+                Ok((
+                    Arc::new(catalogue.games_dao().clone()),
+                    Arc::new(catalogue.missions_dao().clone()),
+                ))
+            }
+        }
+    }
+
+    /// Serves the configured world records file, otherwise the dataset shipped in the binary.
+    fn world_records(
+        config: &CatalogueConfig,
+        missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+    ) -> Result<InMemoryWorldRecordsDao, Error> {
+        let csv = match config.world_records_path() {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|err| CatalogueError::Io(err.to_string()).pipe(Error::Catalogue))?,
+            None => InMemoryWorldRecordsDao::DEFAULT.to_string(),
+        };
+
+        InMemoryWorldRecordsDao::try_from_csv(&csv, missions_dao)
+    }
+
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let (games_dao, missions_dao) = Self::catalogue_daos(config.catalogue())?;
+        let localisations_dao: Arc<dyn LocalisationsDao + Send + Sync> =
+            Arc::new(InMemoryLocalisationsDao::default());
+        let halo_waypoint = InMemoryCacheClient::new(
+            HyperClient::new(config.halo_waypoint().endpoints()),
+            config.cache(),
+        );
+        let service_records_doa = ServiceRecordsDao::new(
+            games_dao.clone(),
+            missions_dao.clone(),
+            localisations_dao.clone(),
+            config.halo_waypoint().credentials(),
+            Box::new(halo_waypoint),
+        );
+
+        let world_records_dao = Self::world_records(config.catalogue(), missions_dao.clone())?;
+
+        Ok(Self {
+            games_dao,
+            missions_dao,
+            localisations_dao,
+            service_records_doa: Arc::new(service_records_doa),
+            world_records_dao: Arc::new(world_records_dao),
+            accept_language: vec![],
+            freshness: Arc::new(Mutex::new(vec![])),
+            warnings: Arc::new(Mutex::new(vec![])),
+        })
+    }
 }
 
 impl juniper::Context for Context {}
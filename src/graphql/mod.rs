@@ -1,4 +1,4 @@
-mod context;
+pub mod context;
 mod models;
 mod query;
 pub mod service;
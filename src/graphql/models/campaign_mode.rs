@@ -1,4 +1,4 @@
-use juniper::{ParseScalarResult, Value};
+use juniper::{ParseScalarResult, ParseScalarValue, Value};
 
 use crate::campaign_modes::campaign_mode::CampaignMode;
 
@@ -17,11 +17,15 @@ where
         Value::scalar(value.to_string())
     }
 
-    fn from_input_value(_v: &InputValue) -> Option<Self> {
-        unimplemented!();
+    fn from_input_value(v: &InputValue) -> Option<Self> {
+        match v.as_string_value()? {
+            "solo" => Some(Self::Solo),
+            "coop" => Some(Self::Coop),
+            _ => None,
+        }
     }
 
-    fn from_str<'a>(_value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
-        unimplemented!();
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
     }
 }
@@ -1,4 +1,4 @@
-use juniper::{ParseScalarResult, Value};
+use juniper::{ParseScalarResult, ParseScalarValue, Value};
 
 use crate::difficulties::difficulty::Difficulty;
 
@@ -19,11 +19,17 @@ where
         Value::scalar(value.to_string())
     }
 
-    fn from_input_value(_v: &InputValue) -> Option<Self> {
-        unimplemented!();
+    fn from_input_value(v: &InputValue) -> Option<Self> {
+        match v.as_string_value()? {
+            "easy" => Some(Self::Easy),
+            "normal" => Some(Self::Normal),
+            "heroic" => Some(Self::Heroic),
+            "legendary" => Some(Self::Legendary),
+            _ => None,
+        }
     }
 
-    fn from_str<'a>(_value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
-        unimplemented!();
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
     }
 }
@@ -1,8 +1,11 @@
-use juniper::{graphql_object, ParseScalarResult, Value};
+use juniper::{graphql_object, FieldResult, ParseScalarResult, Value};
 
+use crate::gamertags::gamertag::Gamertag;
 use crate::games::game::{Game, GameId};
 use crate::graphql::context::Context;
+use crate::locales::locale::Locale;
 use crate::missions::mission::Mission;
+use crate::platforms::platform::Platform;
 use crate::service_records::service_record::ServiceRecord;
 
 #[juniper::graphql_scalar(description = "Game id")]
@@ -30,8 +33,40 @@ impl Game {
         self.id()
     }
 
-    fn name(&self) -> String {
-        self.name()
+    fn name(&self, locale: Option<Locale>, context: &Context) -> String {
+        context
+            .locales(locale)
+            .iter()
+            .find_map(|locale| {
+                context
+                    .localisations_dao()
+                    .find_game_name(self.id(), locale)
+            })
+            .unwrap_or_else(|| self.name())
+    }
+
+    fn short_code(&self) -> String {
+        self.short_code()
+    }
+
+    fn release_year(&self) -> i32 {
+        self.release_year()
+    }
+
+    fn developer(&self) -> String {
+        self.developer()
+    }
+
+    fn halo_waypoint_key(&self) -> String {
+        self.halo_waypoint_key()
+    }
+
+    fn mission_count(&self) -> i32 {
+        self.mission_count()
+    }
+
+    fn menu_order(&self) -> i32 {
+        self.menu_order()
     }
 
     fn missions(&self, context: &Context) -> Vec<Mission> {
@@ -40,12 +75,18 @@ impl Game {
 
     async fn service_record_by_player(
         &self,
-        player: String,
+        player: Gamertag,
+        platform: Option<Platform>,
+        locale: Option<Locale>,
         context: &Context,
-    ) -> Option<Vec<ServiceRecord>> {
-        context
+    ) -> FieldResult<Vec<ServiceRecord>> {
+        let platform = platform.unwrap_or_default();
+        let locale = locale.unwrap_or_default();
+        let fetched = context
             .service_records_doa()
-            .find_by_player_and_game(player, self.clone())
-            .await
+            .find_by_player_and_game(player, self.clone(), platform, locale)
+            .await?;
+
+        Ok(context.record(fetched))
     }
 }
@@ -0,0 +1,22 @@
+use juniper::{ParseScalarResult, ParseScalarValue, Value};
+
+use crate::gamertags::gamertag::Gamertag;
+
+#[juniper::graphql_scalar(description = "Xbox gamertag")]
+impl<S> GraphQLScalar for Gamertag
+where
+    S: ScalarValue,
+{
+    // Define how to convert your custom scalar into a primitive type.
+    fn resolve(&self) -> Value {
+        Value::scalar(self.value().to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Self> {
+        v.as_string_value().and_then(|v| Self::try_from_str(v).ok())
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
@@ -1,8 +1,14 @@
-use juniper::{graphql_object, ParseScalarResult, Value};
+use juniper::{graphql_object, FieldResult, ParseScalarResult, Value};
+use time::Date;
 
+use crate::chainable::Chainable;
+use crate::gamertags::gamertag::Gamertag;
 use crate::graphql::context::Context;
 use crate::graphql::models::time::Time;
-use crate::missions::mission::{Mission, MissionId};
+use crate::locales::locale::Locale;
+use crate::missions::mission::{Mission, MissionId, MissionKind};
+use crate::missions::par::Par;
+use crate::platforms::platform::Platform;
 use crate::service_records::service_record::ServiceRecord;
 
 #[juniper::graphql_scalar(description = "Mission id")]
@@ -30,8 +36,16 @@ impl Mission {
         self.id()
     }
 
-    fn name(&self) -> String {
-        self.name()
+    fn name(&self, locale: Option<Locale>, context: &Context) -> String {
+        context
+            .locales(locale)
+            .iter()
+            .find_map(|locale| {
+                context
+                    .localisations_dao()
+                    .find_mission_name(self.game_id(), self.id(), locale)
+            })
+            .unwrap_or_else(|| self.name())
     }
 
     // TODO: GraphQL shouldn't require explicit conversions
@@ -44,14 +58,73 @@ impl Mission {
         self.par_score()
     }
 
+    /// The current par unless `asOf` (YYYY-MM-DD) or `version` picks an older one.
+    fn par(&self, as_of: Option<String>, version: Option<String>) -> FieldResult<Option<Par>> {
+        let par = match (as_of, version) {
+            (Some(as_of), None) => Date::parse(&as_of, "%F")
+                .map_err(|_| "asOf must be formatted as YYYY-MM-DD")?
+                .pipe(|date| self.par_as_of(date)),
+            (None, Some(version)) => self.par_by_version(&version),
+            (None, None) => self.current_par(),
+            (Some(_), Some(_)) => return Err("Only one of asOf or version is allowed".into()),
+        };
+
+        Ok(par)
+    }
+
+    fn pars(&self) -> Vec<Par> {
+        self.pars()
+    }
+
+    fn kind(&self) -> MissionKind {
+        self.kind()
+    }
+
+    fn ordinal(&self) -> i32 {
+        self.ordinal()
+    }
+
+    fn playable(&self) -> bool {
+        self.is_playable()
+    }
+
+    fn next(&self, context: &Context) -> Option<Mission> {
+        context
+            .missions_dao()
+            .all_by_game_id(self.game_id())
+            .into_iter()
+            .find(|mission| mission.ordinal() > self.ordinal())
+    }
+
+    fn previous(&self, context: &Context) -> Option<Mission> {
+        context
+            .missions_dao()
+            .all_by_game_id(self.game_id())
+            .into_iter()
+            .rev()
+            .find(|mission| mission.ordinal() < self.ordinal())
+    }
+
+    fn halo_waypoint_id(&self, context: &Context) -> Option<i32> {
+        context
+            .missions_dao()
+            .find_halo_waypoint_id(self.game_id(), self.id())
+    }
+
     async fn service_record_by_player(
         &self,
-        player: String,
+        player: Gamertag,
+        platform: Option<Platform>,
+        locale: Option<Locale>,
         context: &Context,
-    ) -> Option<ServiceRecord> {
-        context
+    ) -> FieldResult<Option<ServiceRecord>> {
+        let platform = platform.unwrap_or_default();
+        let locale = locale.unwrap_or_default();
+        let fetched = context
             .service_records_doa()
-            .find_by_player_and_mission(player, self)
-            .await
+            .find_by_player_and_mission(player, self, platform, locale)
+            .await?;
+
+        Ok(context.record(fetched))
     }
 }
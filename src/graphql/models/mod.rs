@@ -1,6 +1,10 @@
 mod campaign_mode;
 mod difficulty;
 mod game;
+mod gamertag;
+mod locale;
 mod mission;
+mod par;
+mod platform;
 mod service_record;
 mod time;
@@ -0,0 +1,25 @@
+use juniper::graphql_object;
+
+use crate::graphql::context::Context;
+use crate::graphql::models::time::Time;
+use crate::missions::par::Par;
+
+#[graphql_object(Context = Context)]
+impl Par {
+    fn version(&self) -> String {
+        self.version()
+    }
+
+    fn effective_from(&self) -> String {
+        self.effective_from().format("%F")
+    }
+
+    // TODO: GraphQL shouldn't require explicit conversions
+    fn time_in_seconds(&self) -> Option<i32> {
+        self.time().map(|time| Time::from_time(&time).seconds())
+    }
+
+    fn score(&self) -> Option<i32> {
+        self.score()
+    }
+}
@@ -0,0 +1,31 @@
+use juniper::{ParseScalarResult, ParseScalarValue, Value};
+
+use crate::platforms::platform::Platform;
+
+#[juniper::graphql_scalar(description = "Platform")]
+impl<S> GraphQLScalar for Platform
+where
+    S: ScalarValue,
+{
+    // Define how to convert your custom scalar into a primitive type.
+    fn resolve(&self) -> Value {
+        let value = match self {
+            Self::XboxOne => "xbox-one",
+            Self::Pc => "pc",
+        };
+
+        Value::scalar(value.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Self> {
+        match v.as_string_value()? {
+            "xbox-one" => Some(Self::XboxOne),
+            "pc" => Some(Self::Pc),
+            _ => None,
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
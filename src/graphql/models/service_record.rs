@@ -2,15 +2,16 @@ use juniper::graphql_object;
 
 use crate::campaign_modes::campaign_mode::CampaignMode;
 use crate::difficulties::difficulty::Difficulty;
+use crate::gamertags::gamertag::Gamertag;
 use crate::games::game::Game;
 use crate::graphql::context::Context;
 use crate::graphql::models::time::Time;
 use crate::missions::mission::Mission;
-use crate::service_records::service_record::{ServiceRecord, ServiceRecordRun};
+use crate::service_records::service_record::{ServiceRecord, ServiceRecordBest, ServiceRecordRun};
 
 #[graphql_object(Context = Context)]
 impl ServiceRecord {
-    fn player(&self) -> String {
+    fn player(&self) -> Gamertag {
         self.player()
     }
 
@@ -35,6 +36,26 @@ impl ServiceRecord {
     fn runs(&self) -> Vec<ServiceRecordRun> {
         self.runs()
     }
+
+    /// Best time and score at `difficultyAtLeast` or harder, a Legendary clear counting for Heroic.
+    fn best(
+        &self,
+        difficulty_at_least: Difficulty,
+        campaign_mode: CampaignMode,
+    ) -> Option<ServiceRecordBest> {
+        self.best(difficulty_at_least, campaign_mode)
+    }
+}
+
+#[graphql_object(Context = Context)]
+impl ServiceRecordBest {
+    fn time_in_seconds(&self) -> Time {
+        Time::from_time(&self.time())
+    }
+
+    fn score(&self) -> i32 {
+        self.score()
+    }
 }
 
 #[graphql_object(Context = Context)]
@@ -54,4 +75,34 @@ impl ServiceRecordRun {
     fn score(&self) -> i32 {
         self.score()
     }
+
+    fn world_record_time(&self, context: &Context) -> Option<Time> {
+        context
+            .world_records_dao()
+            .find_by_run(self)
+            .map(|world_record| Time::from_time(&world_record.time()))
+    }
+
+    /// Seconds behind the world record, negative when the run beats it.
+    fn delta_to_world_record(&self, context: &Context) -> Option<i32> {
+        context
+            .world_records_dao()
+            .find_by_run(self)
+            .map(|world_record| {
+                Time::from_time(&self.time()).seconds()
+                    - Time::from_time(&world_record.time()).seconds()
+            })
+    }
+
+    /// The run's time as a percentage of the world record, 100 being a tie.
+    fn percent_of_world_record(&self, context: &Context) -> Option<f64> {
+        context
+            .world_records_dao()
+            .find_by_run(self)
+            .map(|world_record| Time::from_time(&world_record.time()).seconds())
+            .filter(|world_record| *world_record > 0)
+            .map(|world_record| {
+                f64::from(Time::from_time(&self.time()).seconds()) * 100.0 / f64::from(world_record)
+            })
+    }
 }
@@ -1,7 +1,11 @@
 use juniper::{graphql_object, FieldResult};
 
+use crate::cli::format::Format;
+use crate::gamertags::gamertag::Gamertag;
 use crate::games::game::{Game, GameId};
 use crate::graphql::context::Context;
+use crate::locales::locale::Locale;
+use crate::platforms::platform::Platform;
 
 pub struct Query;
 
@@ -11,7 +15,44 @@ impl Query {
         Ok(context.games_dao().all())
     }
 
-    fn game(context: &Context, id: i32) -> FieldResult<Option<Game>> {
-        Ok(context.games_dao().find_by_id(GameId::new(id)))
+    fn game(
+        context: &Context,
+        id: Option<i32>,
+        short_code: Option<String>,
+    ) -> FieldResult<Option<Game>> {
+        let game = match (id, short_code) {
+            (Some(id), None) => context.games_dao().find_by_id(GameId::new(id)),
+            (None, Some(short_code)) => context.games_dao().find_by_short_code(&short_code),
+            (Some(_), Some(_)) => return Err("Only one of id or shortCode may be given".into()),
+            (None, None) => return Err("Either id or shortCode is required".into()),
+        };
+
+        Ok(game)
+    }
+
+    /// CSV with one row per game, mission, mode and difficulty, every game unless `gameId` is given.
+    async fn service_records_csv(
+        context: &Context,
+        player: Gamertag,
+        game_id: Option<i32>,
+        platform: Option<Platform>,
+        locale: Option<Locale>,
+    ) -> FieldResult<String> {
+        let games = match game_id {
+            Some(id) => match context.games_dao().find_by_id(GameId::new(id)) {
+                Some(game) => vec![game],
+                None => return Err("Unknown game".into()),
+            },
+            None => context.games_dao().all(),
+        };
+
+        let platform = platform.unwrap_or_default();
+        let locale = locale.unwrap_or_default();
+        let fetched = context
+            .service_records_doa()
+            .export_by_player(player, games, platform, locale)
+            .await?;
+
+        Ok(Format::Csv.render(&context.record(fetched)))
     }
 }
@@ -1,9 +1,23 @@
-use hyper::{Body, Request, Response};
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, Method, Request, Response, StatusCode, Uri};
+use itertools::Itertools;
 use juniper::{EmptyMutation, EmptySubscription, RootNode};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
+use tracing::info_span;
+use tracing_futures::Instrument;
+use url::form_urlencoded;
 
+use crate::configs::config::Config;
+use crate::error::Error;
 use crate::graphql::context::Context;
 use crate::graphql::query::Query;
+use crate::halo_waypoint::freshness::Freshness;
+use crate::locales::locale::Locale;
+use crate::metrics::registry::{GRAPHQL_LATENCY, GRAPHQL_REQUESTS};
 
 #[derive(Clone)]
 pub struct Service {
@@ -21,17 +35,366 @@ impl Service {
     }
 
     pub async fn graphql(&self, req: Request<Body>) -> Result<Response<Body>, hyper::error::Error> {
-        juniper_hyper::graphql(self.root_node.clone(), self.context.clone(), req).await
+        let accept_language = req
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(Locale::from_accept_language)
+            .unwrap_or_default();
+        let context = Arc::new(self.context.for_request(accept_language));
+        // Only GET queries are cacheable, POST bodies aren't part of the cache key.
+        let cacheable = req.method() == Method::GET;
+        let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+
+        let start = Instant::now();
+        // juniper_hyper reads the body itself, it is buffered here to find the operation name.
+        let (parts, body) = req.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        let operation = operation_label(&parts.method, &parts.uri, &body);
+        let req = Request::from_parts(parts, Body::from(body));
+
+        let res = juniper_hyper::graphql(self.root_node.clone(), context.clone(), req)
+            .instrument(info_span!("graphql", operation = %operation))
+            .await;
+
+        if let Ok(res) = &res {
+            let status = res.status().as_str().to_string();
+            GRAPHQL_REQUESTS
+                .with_label_values(&[&operation, &status])
+                .inc();
+            GRAPHQL_LATENCY
+                .with_label_values(&[&operation, &status])
+                .observe(start.elapsed().as_secs_f64());
+        }
+
+        let res = match res {
+            Ok(res) if res.status() == StatusCode::OK => {
+                Self::with_warnings(res, context.warnings()).await
+            }
+            res => res,
+        };
+
+        match res {
+            Ok(res) if cacheable && res.status() == StatusCode::OK => {
+                Self::with_cache_headers(res, context.freshness(), if_none_match.as_ref()).await
+            }
+            res => res,
+        }
+    }
+
+    /// Lists the inconsistencies met in service records under `extensions.warnings`, next to
+    /// the data they didn't prevent.
+    async fn with_warnings(
+        res: Response<Body>,
+        warnings: Vec<Error>,
+    ) -> Result<Response<Body>, hyper::error::Error> {
+        if warnings.is_empty() {
+            return Ok(res);
+        }
+
+        let (mut parts, body) = res.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        let body = match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Object(mut object)) => {
+                let warnings = warnings
+                    .iter()
+                    .map(Error::to_string)
+                    .unique()
+                    .map(|message| json!({ "message": message }))
+                    .collect::<Vec<Value>>();
+                object.insert("extensions".to_string(), json!({ "warnings": warnings }));
+
+                parts.headers.remove(header::CONTENT_LENGTH);
+                serde_json::to_vec(&object).unwrap().into()
+            }
+            _ => body,
+        };
+
+        Ok(Response::from_parts(parts, Body::from(body)))
     }
 
-    pub fn default() -> Self {
-        Self {
-            context: Arc::new(Context::default()),
+    /// Weak `ETag` over the body, and a lifetime taken from the stalest cached page the query read.
+    ///
+    /// Queries that read no service record, or one that isn't cached, must be revalidated, and so
+    /// must answers with errors: the pages that failed may not have been cached.
+    async fn with_cache_headers(
+        res: Response<Body>,
+        freshness: Option<Option<Freshness>>,
+        if_none_match: Option<&HeaderValue>,
+    ) -> Result<Response<Body>, hyper::error::Error> {
+        let (mut parts, body) = res.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        let etag = etag(&body);
+
+        let headers = &mut parts.headers;
+        match freshness {
+            Some(Some(freshness)) if !has_errors(&body) => {
+                headers.insert(
+                    header::CACHE_CONTROL,
+                    HeaderValue::from_str(&format!(
+                        "public, max-age={}",
+                        freshness.ttl().as_secs()
+                    ))
+                    .unwrap(),
+                );
+                headers.insert(header::AGE, HeaderValue::from(freshness.age().as_secs()));
+            }
+            _ => {
+                headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+            }
+        }
+        headers.insert(header::ETAG, HeaderValue::from_str(&etag).unwrap());
+        headers.append(header::VARY, HeaderValue::from_static("Accept-Language"));
+
+        if if_none_match.map_or(false, |if_none_match| matches(if_none_match, &etag)) {
+            parts.status = StatusCode::NOT_MODIFIED;
+            return Ok(Response::from_parts(parts, Body::empty()));
+        }
+
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        Ok(Self {
+            context: Arc::new(Context::new(config)?),
             root_node: Arc::new(RootNode::new(
                 Query,
                 EmptyMutation::<Context>::new(),
                 EmptySubscription::<Context>::new(),
             )),
+        })
+    }
+}
+
+/// Root fields of the schema, the only operation names used as metrics labels.
+const ROOT_FIELDS: [&str; 3] = ["game", "games", "serviceRecordsCsv"];
+
+/// Metrics label: `operationName`, otherwise the first named operation of the query.
+///
+/// Clients choose the names, so only the schema's root fields are kept, anything else is
+/// `other`. Each label value is a Prometheus series held until the process exits.
+fn operation_label(method: &Method, uri: &Uri, body: &[u8]) -> String {
+    let (operation_name, query) = if method == Method::GET {
+        let params = uri
+            .query()
+            .map(|query| {
+                form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect::<HashMap<String, String>>()
+            })
+            .unwrap_or_default();
+
+        (
+            params.get("operationName").cloned(),
+            params.get("query").cloned(),
+        )
+    } else {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Array(_)) => return "batch".to_string(),
+            Ok(body) => (
+                body["operationName"].as_str().map(str::to_string),
+                body["query"].as_str().map(str::to_string),
+            ),
+            // `application/graphql` bodies are the query itself.
+            Err(_) => (None, String::from_utf8(body.to_vec()).ok()),
         }
+    };
+
+    let operation_name = operation_name.or_else(|| {
+        Regex::new(r"(?:query|mutation|subscription)\s+([_A-Za-z][_0-9A-Za-z]*)")
+            .unwrap()
+            .captures(&query?)?
+            .get(1)
+            .map(|name| name.as_str().to_string())
+    });
+
+    match operation_name {
+        None => "anonymous",
+        Some(operation_name) => ROOT_FIELDS
+            .iter()
+            .find(|field| field.eq_ignore_ascii_case(&operation_name))
+            .copied()
+            .unwrap_or("other"),
+    }
+    .to_string()
+}
+
+/// Whether the answer, or one of a batch, lists errors.
+fn has_errors(body: &[u8]) -> bool {
+    let errors = |res: &Value| {
+        res["errors"]
+            .as_array()
+            .map_or(false, |errors| !errors.is_empty())
+    };
+
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(batch)) => batch.iter().any(errors),
+        Ok(res) => errors(&res),
+        Err(_) => true,
+    }
+}
+
+/// FNV-1a, so a tag outlives upgrades of the standard library's hasher and restarts.
+fn etag(body: &[u8]) -> String {
+    let hash = body.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    format!("W/\"{:016x}\"", hash)
+}
+
+/// Weak comparison, as `If-None-Match` requires.
+fn matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+
+    if_none_match
+        .to_str()
+        .map(|tags| {
+            tags.split(',')
+                .any(|tag| tag.trim() == "*" || weak(tag) == weak(etag))
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod service_test {
+    use super::*;
+    use crate::error::HaloWaypointError;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cache_headers_from_freshness() {
+        let freshness = Freshness::new(Duration::from_secs(120), Duration::from_secs(600));
+        let res = Service::with_cache_headers(
+            Response::new(Body::from("{}")),
+            Some(Some(freshness)),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CACHE_CONTROL], "public, max-age=600");
+        assert_eq!(res.headers()[header::AGE], "120");
+        assert_eq!(res.headers()[header::ETAG], etag(b"{}").as_str());
+    }
+
+    #[tokio::test]
+    async fn no_cache_with_errors() {
+        let freshness = Freshness::new(Duration::from_secs(120), Duration::from_secs(600));
+        let res = Service::with_cache_headers(
+            Response::new(Body::from(
+                r#"{"data":null,"errors":[{"message":"Player not found"}]}"#,
+            )),
+            Some(Some(freshness)),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.headers()[header::CACHE_CONTROL], "no-cache");
+        assert!(!res.headers().contains_key(header::AGE));
+    }
+
+    #[test]
+    fn stable_etag() {
+        assert_eq!(etag(b""), "W/\"cbf29ce484222325\"");
+        assert_eq!(etag(b"{}"), "W/\"08f44b07b5901a25\"");
+    }
+
+    #[tokio::test]
+    async fn warnings_in_extensions() {
+        let warning = Error::HaloWaypoint(HaloWaypointError::UnknownMissionId(999));
+        let res = Service::with_warnings(
+            Response::new(Body::from(r#"{"data":{"games":[]}}"#)),
+            vec![warning.clone(), warning],
+        )
+        .await
+        .unwrap();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({
+                "data": { "games": [] },
+                "extensions": {
+                    "warnings": [{ "message": "Halo Waypoint returned unknown mission 999" }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn operation_labels() {
+        let get = |query: &str| {
+            operation_label(
+                &Method::GET,
+                &format!("/graphql?{}", query).parse().unwrap(),
+                b"",
+            )
+        };
+        let post = |body: &str| {
+            operation_label(&Method::POST, &"/graphql".parse().unwrap(), body.as_bytes())
+        };
+
+        assert_eq!(get("query=query+Games+%7B+games+%7B+id+%7D+%7D"), "games");
+        assert_eq!(get("query=%7B+games+%7B+id+%7D+%7D"), "anonymous");
+        assert_eq!(
+            post(
+                r#"{"query":"query a { games { id } } query game { game(id: 1) { id } }","operationName":"game"}"#
+            ),
+            "game"
+        );
+        assert_eq!(post(r#"{"query":"{ games { id } }"}"#), "anonymous");
+        assert_eq!(post(r#"[{"query":"{ games { id } }"}]"#), "batch");
+        assert_eq!(
+            post(r#"{"query":"query GetGames1234 { games { id } }"}"#),
+            "other"
+        );
+    }
+
+    #[tokio::test]
+    async fn root_fields() {
+        let service = Service::new(&Config::default()).unwrap();
+        let (res, errors) = juniper::execute(
+            "{ __schema { queryType { fields { name } } } }",
+            None,
+            &service.root_node,
+            &juniper::Variables::new(),
+            &*service.context,
+        )
+        .await
+        .unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            serde_json::to_value(&res).unwrap()["__schema"]["queryType"]["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(|field| field["name"].as_str())
+                .sorted()
+                .collect::<Vec<&str>>(),
+            ROOT_FIELDS.to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn not_modified() {
+        let if_none_match = HeaderValue::from_str(&etag(b"{}").replace("W/", "")).unwrap();
+        let res = Service::with_cache_headers(
+            Response::new(Body::from("{}")),
+            None,
+            Some(&if_none_match),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers()[header::CACHE_CONTROL], "no-cache");
+        assert!(hyper::body::to_bytes(res.into_body())
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
@@ -3,14 +3,24 @@ use http::header::HeaderValue;
 use http::{header, Request, Response};
 use std::convert::TryFrom;
 use std::hash::Hash;
-use std::sync::RwLock;
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, info, info_span};
+use tracing_futures::Instrument;
 use ttl_cache::TtlCache;
 
 use crate::chainable::Chainable;
+use crate::configs::config::CacheConfig;
 use crate::error::Error;
+use crate::halo_waypoint::endpoints::Endpoints;
+use crate::halo_waypoint::freshness::Freshness;
+use crate::halo_waypoint::health::{AuthHealth, ClientHealth};
 use crate::halo_waypoint::requests::auth::*;
 use crate::halo_waypoint::requests::service_record::*;
+use crate::metrics::registry::{
+    record_parse_failure, CACHE_EVICTIONS, CACHE_REQUESTS, LOGINS, UPSTREAM_LATENCY,
+    UPSTREAM_REQUESTS,
+};
 
 #[async_trait]
 pub trait Client {
@@ -20,6 +30,19 @@ pub trait Client {
         auth: &GetAuthResponse,
         req: &GetServiceRecordRequest,
     ) -> Result<GetServiceRecordResponse, Error>;
+    fn health(&self, req: Option<&GetAuthRequest>) -> ClientHealth;
+
+    /// How long ago the answer to `req` was fetched with `auth`, `None` when it isn't cached.
+    fn freshness(
+        &self,
+        _auth: &GetAuthResponse,
+        _req: &GetServiceRecordRequest,
+    ) -> Option<Freshness> {
+        None
+    }
+
+    /// Persists whatever must survive a restart, called once on shutdown.
+    async fn flush(&self) {}
 }
 
 #[derive(Clone)]
@@ -28,10 +51,12 @@ pub struct HyperClient {
         hyper_tls::HttpsConnector<hyper::client::HttpConnector>,
         hyper::body::Body,
     >,
+    endpoints: Endpoints,
+    last_success: Arc<RwLock<Option<SystemTime>>>,
 }
 
 impl HyperClient {
-    async fn request<Req, Res>(&self, req: Req) -> Result<Res, Error>
+    async fn request<Req, Res>(&self, endpoint: &str, req: Req) -> Result<Res, Error>
     where
         Request<hyper::body::Body>: From<Req>,
         Res: TryFrom<Response<String>, Error = Error>,
@@ -42,7 +67,33 @@ impl HyperClient {
             HeaderValue::from_static("halomcc.run/0.1"),
         );
 
-        let res = self.client.request(req).await.map_err(Error::from_hyper)?;
+        // The query string may carry the OAuth code, only the path is logged.
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let start = Instant::now();
+
+        let res = self.client.request(req).await;
+        UPSTREAM_LATENCY
+            .with_label_values(&[endpoint])
+            .observe(start.elapsed().as_secs_f64());
+
+        let status = res
+            .as_ref()
+            .map_or("error".to_string(), |res| res.status().as_str().to_string());
+        UPSTREAM_REQUESTS
+            .with_label_values(&[endpoint, &status])
+            .inc();
+
+        let res = res.map_err(Error::from_hyper)?;
+        info!(
+            endpoint,
+            method = %method,
+            path = %path,
+            status = res.status().as_u16(),
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "upstream response"
+        );
+
         let res_without_body = res
             .headers()
             .into_iter()
@@ -58,26 +109,47 @@ impl HyperClient {
             .pipe(String::from_utf8)
             .unwrap();
 
-        Res::try_from(res_without_body.body(body).unwrap())
+        let res = Res::try_from(res_without_body.body(body).unwrap());
+        match &res {
+            Ok(_) => *self.last_success.write().unwrap() = Some(SystemTime::now()),
+            Err(err) => record_parse_failure(err),
+        }
+
+        res
     }
 
-    fn default() -> Self {
+    pub fn new(endpoints: Endpoints) -> Self {
         let https = hyper_tls::HttpsConnector::new();
         let client = hyper::Client::builder().build(https);
 
-        Self { client }
+        Self {
+            client,
+            endpoints,
+            last_success: Arc::new(RwLock::new(None)),
+        }
     }
 }
 
 #[async_trait]
 impl Client for HyperClient {
     async fn get_auth(&self, req: &GetAuthRequest) -> Result<GetAuthResponse, Error> {
-        let res: GetAuthRequestForm = self.request(&GetAuthRequestGetForm).await?;
-        let req: GetAuthRequestPostForm = GetAuthRequestPostForm::new(&req, &res);
-        let req: GetAuthRequestRedirect = self.request(&req).await?;
-        let res: GetAuthResponse = self.request(&req).await?;
+        let res = async {
+            let res: GetAuthRequestForm = self
+                .request("authorize", &GetAuthRequestGetForm::new(&self.endpoints))
+                .await?;
+            let req: GetAuthRequestPostForm = GetAuthRequestPostForm::new(&req, &res);
+            let req: GetAuthRequestRedirect = self.request("login", &req).await?;
+            let res: GetAuthResponse = self.request("callback", &req).await?;
 
-        Ok(res)
+            Ok(res)
+        }
+        .await;
+
+        LOGINS
+            .with_label_values(&[if res.is_ok() { "ok" } else { "error" }])
+            .inc();
+
+        res
     }
 
     async fn get_service_record(
@@ -85,19 +157,29 @@ impl Client for HyperClient {
         auth: &GetAuthResponse,
         req: &GetServiceRecordRequest,
     ) -> Result<GetServiceRecordResponse, Error> {
-        let req = AuthenticatedGetServiceRecord::new(auth.clone(), req.clone());
-        self.request(&req).await
+        let req =
+            AuthenticatedGetServiceRecord::new(self.endpoints.clone(), auth.clone(), req.clone());
+        self.request("service_record", &req).await
+    }
+
+    fn health(&self, req: Option<&GetAuthRequest>) -> ClientHealth {
+        ClientHealth::new(
+            req.is_some(),
+            AuthHealth::Missing,
+            *self.last_success.read().unwrap(),
+        )
     }
 }
 
+/// Entries keep their insertion time so responses can advertise their age.
 pub struct InMemoryCacheClient<A: Client> {
     client: A,
-    auth_cache: RwLock<TtlCache<GetAuthRequest, Result<GetAuthResponse, Error>>>,
+    auth_cache: RwLock<TtlCache<GetAuthRequest, (Instant, Result<GetAuthResponse, Error>)>>,
     auth_cache_ttl: Duration,
     service_record_cache: RwLock<
         TtlCache<
             (GetAuthResponse, GetServiceRecordRequest),
-            Result<GetServiceRecordResponse, Error>,
+            (Instant, Result<GetServiceRecordResponse, Error>),
         >,
     >,
     service_record_cache_ttl: Duration,
@@ -106,6 +188,7 @@ pub struct InMemoryCacheClient<A: Client> {
 impl<A: Client> InMemoryCacheClient<A> {
     async fn request<Req, Res, FutureRes, Execute, GetCache, GetCacheTtl>(
         &self,
+        cache: &str,
         req: &Req,
         execute: Execute,
         get_cache: GetCache,
@@ -116,17 +199,37 @@ impl<A: Client> InMemoryCacheClient<A> {
         Res: Clone,
         FutureRes: std::future::Future<Output = Result<Res, Error>> + std::marker::Send,
         Execute: FnOnce(&Self, &Req) -> FutureRes,
-        GetCache: FnOnce(&Self) -> &RwLock<TtlCache<Req, Result<Res, Error>>> + Copy,
+        GetCache: FnOnce(&Self) -> &RwLock<TtlCache<Req, (Instant, Result<Res, Error>)>> + Copy,
         GetCacheTtl: FnOnce(&Self) -> &Duration,
     {
-        let res = get_cache(self).read().unwrap().get(req).cloned();
+        let res = get_cache(self)
+            .read()
+            .unwrap()
+            .get(req)
+            .map(|(_, res)| res.clone());
         match res {
-            Some(res) => res,
+            Some(res) => {
+                debug!(cache, result = "hit");
+                CACHE_REQUESTS.with_label_values(&[cache, "hit"]).inc();
+                res
+            }
             None => {
+                debug!(cache, result = "miss");
+                CACHE_REQUESTS.with_label_values(&[cache, "miss"]).inc();
                 let res = execute(self, req).await;
-                get_cache(self).write().unwrap().insert(
+
+                let mut ttl_cache = get_cache(self).write().unwrap();
+                // TtlCache::iter drops the expired entries, which sit at the front as a cache has a
+                // single TTL, so the size hint counts the live ones without scanning. A full cache
+                // of live entries means an eviction.
+                if ttl_cache.iter().size_hint().0 >= ttl_cache.capacity()
+                    && !ttl_cache.contains_key(req)
+                {
+                    CACHE_EVICTIONS.with_label_values(&[cache]).inc();
+                }
+                ttl_cache.insert(
                     req.clone(),
-                    res.clone(),
+                    (Instant::now(), res.clone()),
                     *get_cache_ttl(self),
                 );
 
@@ -136,15 +239,13 @@ impl<A: Client> InMemoryCacheClient<A> {
     }
 }
 
-impl Default for InMemoryCacheClient<HyperClient> {
-    fn default() -> Self {
-        let client = HyperClient::default();
-
-        let auth_cache = RwLock::new(TtlCache::new(10));
-        let auth_cache_ttl = Duration::from_secs(14400);
+impl<A: Client> InMemoryCacheClient<A> {
+    pub fn new(client: A, config: &CacheConfig) -> Self {
+        let auth_cache = RwLock::new(TtlCache::new(config.auth_capacity()));
+        let auth_cache_ttl = config.auth_ttl();
 
-        let service_record_cache = RwLock::new(TtlCache::new(1000));
-        let service_record_cache_ttl = Duration::from_secs(600);
+        let service_record_cache = RwLock::new(TtlCache::new(config.service_record_capacity()));
+        let service_record_cache_ttl = config.service_record_ttl();
 
         Self {
             client,
@@ -160,12 +261,14 @@ impl Default for InMemoryCacheClient<HyperClient> {
 impl<A: Client + Sync> Client for InMemoryCacheClient<A> {
     async fn get_auth(&self, req: &GetAuthRequest) -> Result<GetAuthResponse, Error> {
         self.request(
+            "auth",
             req,
             // TODO: fix lifetime
             |_s, _r| self.client.get_auth(req),
             |s| &s.auth_cache,
             |s| &s.auth_cache_ttl,
         )
+        .instrument(info_span!("client", call = "auth"))
         .await
     }
 
@@ -175,40 +278,139 @@ impl<A: Client + Sync> Client for InMemoryCacheClient<A> {
         req: &GetServiceRecordRequest,
     ) -> Result<GetServiceRecordResponse, Error> {
         self.request(
+            "service_record",
             &(auth.clone(), req.clone()),
             // TODO: fix lifetime
             |_s, (_a, _r)| self.client.get_service_record(auth, req),
             |s| &s.service_record_cache,
             |s| &s.service_record_cache_ttl,
         )
+        .instrument(info_span!("client", call = "service_record", request = ?req))
         .await
     }
+
+    fn health(&self, req: Option<&GetAuthRequest>) -> ClientHealth {
+        let auth = match req.and_then(|req| {
+            self.auth_cache
+                .read()
+                .unwrap()
+                .get(req)
+                .map(|(_, res)| res.clone())
+        }) {
+            Some(Ok(_)) => AuthHealth::Valid,
+            Some(Err(_)) => AuthHealth::Invalid,
+            None => AuthHealth::Missing,
+        };
+
+        self.client.health(req).with_auth(auth)
+    }
+
+    fn freshness(
+        &self,
+        auth: &GetAuthResponse,
+        req: &GetServiceRecordRequest,
+    ) -> Option<Freshness> {
+        self.service_record_cache
+            .read()
+            .unwrap()
+            .get(&(auth.clone(), req.clone()))
+            .map(|(inserted, _)| Freshness::new(inserted.elapsed(), self.service_record_cache_ttl))
+    }
+
+    // The caches live in memory only, the wrapped client may still persist something.
+    async fn flush(&self) {
+        self.client.flush().await
+    }
 }
 
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../bin/mock_halo_waypoint.rs"]
+mod mock_halo_waypoint;
+
 #[cfg(test)]
 mod hyper_client_tests {
     use super::*;
+    use crate::gamertags::gamertag::Gamertag;
     use crate::halo_waypoint::models::campaign_mode::CampaignMode;
     use crate::halo_waypoint::models::game::Game;
+    use crate::halo_waypoint::models::locale::Locale;
+    use crate::halo_waypoint::models::platform::Platform;
+    use std::net::SocketAddr;
+
+    fn client() -> HyperClient {
+        let (addr, server) = mock_halo_waypoint::server(
+            SocketAddr::from(([127, 0, 0, 1], 0)),
+            mock_halo_waypoint::Credentials::new("john", "117"),
+        )
+        .unwrap();
+        tokio::spawn(server);
+
+        HyperClient::new(Endpoints::new(
+            &format!("http://{}/oauth20_authorize.srf", addr),
+            "000000004C0BD2F1",
+            &format!("http://{}/auth/callback", addr),
+            &format!("http://{}", addr),
+        ))
+    }
 
     #[tokio::test]
-    #[ignore]
     async fn get_auth() {
-        let req = GetAuthRequest::default();
-        let res = HyperClient::default().get_auth(&req).await;
+        let res = client().get_auth(&GetAuthRequest::new("john", "117")).await;
 
         assert!(res.is_ok());
     }
 
     #[tokio::test]
-    #[ignore]
+    async fn get_auth_with_wrong_password() {
+        let res = client().get_auth(&GetAuthRequest::new("john", "118")).await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
     async fn get_service_record() {
-        let req = GetAuthRequest::default();
-        let auth = HyperClient::default().get_auth(&req).await.unwrap();
+        let client = client();
+        let auth = client
+            .get_auth(&GetAuthRequest::new("john", "117"))
+            .await
+            .unwrap();
 
-        let req = GetServiceRecordRequest::new("John117", &Game::Halo, &CampaignMode::Solo);
-        let res = HyperClient::default().get_service_record(&auth, &req).await;
+        let req = GetServiceRecordRequest::new(
+            &Gamertag::try_from_str("John117").unwrap(),
+            &Game::Halo,
+            &CampaignMode::Solo,
+            &Platform::XboxOne,
+            &Locale::new("en-us"),
+        );
+        let res = client.get_service_record(&auth, &req).await;
 
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn freshness_by_cache_key() {
+        let client = InMemoryCacheClient::new(client(), &CacheConfig::default());
+        let auth = client
+            .get_auth(&GetAuthRequest::new("john", "117"))
+            .await
+            .unwrap();
+
+        let req = |campaign_mode: &CampaignMode| {
+            GetServiceRecordRequest::new(
+                &Gamertag::try_from_str("John117").unwrap(),
+                &Game::Halo,
+                campaign_mode,
+                &Platform::XboxOne,
+                &Locale::new("en-us"),
+            )
+        };
+        client
+            .get_service_record(&auth, &req(&CampaignMode::Solo))
+            .await
+            .unwrap();
+
+        assert!(client.freshness(&auth, &req(&CampaignMode::Solo)).is_some());
+        assert!(client.freshness(&auth, &req(&CampaignMode::Coop)).is_none());
+    }
 }
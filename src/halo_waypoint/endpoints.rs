@@ -0,0 +1,72 @@
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Endpoints {
+    authorize_url: String,
+    client_id: String,
+    redirect_uri: String,
+    halo_waypoint_url: String,
+}
+
+impl Endpoints {
+    pub fn new(
+        authorize_url: &str,
+        client_id: &str,
+        redirect_uri: &str,
+        halo_waypoint_url: &str,
+    ) -> Self {
+        Self {
+            authorize_url: authorize_url.to_string(),
+            client_id: client_id.to_string(),
+            redirect_uri: redirect_uri.to_string(),
+            halo_waypoint_url: halo_waypoint_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn authorize_url(&self) -> String {
+        self.authorize_url.clone()
+    }
+
+    pub fn client_id(&self) -> String {
+        self.client_id.clone()
+    }
+
+    pub fn redirect_uri(&self) -> String {
+        self.redirect_uri.clone()
+    }
+
+    pub fn halo_waypoint_url(&self) -> String {
+        self.halo_waypoint_url.clone()
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+pub fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02x}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod endpoints_test {
+    use super::*;
+
+    #[test]
+    fn encode_reserved() {
+        assert_eq!(
+            encode("https://www.halowaypoint.com/en-us"),
+            "https%3a%2f%2fwww.halowaypoint.com%2fen-us"
+        );
+    }
+
+    #[test]
+    fn new_trims_trailing_slash() {
+        let endpoints = Endpoints::new("", "", "", "http://127.0.0.1:3001/");
+
+        assert_eq!(endpoints.halo_waypoint_url(), "http://127.0.0.1:3001");
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// Age and lifetime of a cached Halo Waypoint answer, as HTTP caches understand them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Freshness {
+    age: Duration,
+    ttl: Duration,
+}
+
+impl Freshness {
+    pub fn new(age: Duration, ttl: Duration) -> Self {
+        Self { age, ttl }
+    }
+
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.ttl.checked_sub(self.age).unwrap_or_default()
+    }
+
+    /// A response built from several entries is only fresh as long as the first one to expire.
+    pub fn stalest(self, other: Self) -> Self {
+        if other.remaining() < self.remaining() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod freshness_test {
+    use super::*;
+
+    #[test]
+    fn stalest() {
+        let fresh = Freshness::new(Duration::from_secs(10), Duration::from_secs(600));
+        let stale = Freshness::new(Duration::from_secs(590), Duration::from_secs(600));
+
+        assert_eq!(fresh.stalest(stale), stale);
+        assert_eq!(stale.stalest(fresh), stale);
+        assert_eq!(stale.remaining(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn remaining_after_ttl() {
+        let expired = Freshness::new(Duration::from_secs(700), Duration::from_secs(600));
+
+        assert_eq!(expired.remaining(), Duration::from_secs(0));
+    }
+}
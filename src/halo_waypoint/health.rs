@@ -0,0 +1,67 @@
+use std::time::SystemTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthHealth {
+    Valid,
+    Invalid,
+    Missing,
+}
+
+/// What the client knows about its ability to reach Halo Waypoint, without calling it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientHealth {
+    credentials_configured: bool,
+    auth: AuthHealth,
+    last_success: Option<SystemTime>,
+}
+
+impl ClientHealth {
+    pub fn new(
+        credentials_configured: bool,
+        auth: AuthHealth,
+        last_success: Option<SystemTime>,
+    ) -> Self {
+        Self {
+            credentials_configured,
+            auth,
+            last_success,
+        }
+    }
+
+    pub fn credentials_configured(&self) -> bool {
+        self.credentials_configured
+    }
+
+    pub fn auth(&self) -> AuthHealth {
+        self.auth
+    }
+
+    pub fn last_success(&self) -> Option<SystemTime> {
+        self.last_success
+    }
+
+    pub fn with_auth(&self, auth: AuthHealth) -> Self {
+        Self {
+            auth,
+            ..self.clone()
+        }
+    }
+
+    /// Records can be served once we can log in, which a failed cached login rules out until it expires.
+    pub fn is_ready(&self) -> bool {
+        self.credentials_configured && self.auth != AuthHealth::Invalid
+    }
+}
+
+#[cfg(test)]
+mod client_health_test {
+    use super::*;
+
+    #[test]
+    fn is_ready() {
+        assert!(ClientHealth::new(true, AuthHealth::Missing, None).is_ready());
+        assert!(ClientHealth::new(true, AuthHealth::Valid, None).is_ready());
+        assert!(!ClientHealth::new(true, AuthHealth::Invalid, None).is_ready());
+        assert!(!ClientHealth::new(false, AuthHealth::Missing, None).is_ready());
+    }
+}
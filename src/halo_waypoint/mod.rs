@@ -1,3 +1,6 @@
 pub mod client;
+pub mod endpoints;
+pub mod freshness;
+pub mod health;
 pub mod models;
 pub mod requests;
@@ -3,6 +3,7 @@ use std::result::Result;
 
 use crate::chainable::Chainable;
 use crate::error::{Error, HaloWaypointError};
+use crate::games::dao::GamesDao;
 use crate::games::game::Game as InternalGame;
 use crate::games::game::GameId as InternalGameId;
 
@@ -62,28 +63,16 @@ impl Game {
             .and_then(Self::try_from_str)
     }
 
-    pub fn from_internal(game: &InternalGame) -> Self {
-        match game.id().value() {
-            1 => Self::Halo,
-            2 => Self::Halo2,
-            3 => Self::Halo3,
-            4 => Self::Halo3Odst,
-            5 => Self::HaloReach,
-            6 => Self::Halo4,
-            other => unreachable!("Internal game id shouldn't exist: {}", other),
-        }
+    pub fn from_internal(game: &InternalGame) -> Result<Self, Error> {
+        Self::try_from_str(&game.halo_waypoint_key())
     }
 
-    pub fn to_internal(&self) -> InternalGameId {
-        let id = match self {
-            Self::Halo => 1,
-            Self::Halo2 => 2,
-            Self::Halo3 => 3,
-            Self::Halo3Odst => 4,
-            Self::HaloReach => 5,
-            Self::Halo4 => 6,
-        };
-
-        InternalGameId::new(id)
+    pub fn to_internal(&self, games_dao: &dyn GamesDao) -> Result<InternalGameId, Error> {
+        games_dao
+            .find_by_halo_waypoint_key(&self.to_string())
+            .map(|game| game.id())
+            .ok_or_else(|| {
+                HaloWaypointError::UnknownGame(self.to_string()).pipe(Error::HaloWaypoint)
+            })
     }
 }
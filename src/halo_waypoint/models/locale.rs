@@ -0,0 +1,19 @@
+use crate::locales::locale::Locale as InternalLocale;
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Locale(String);
+
+impl Locale {
+    pub fn new(locale: &str) -> Self {
+        Self(locale.to_string())
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.0.clone()
+    }
+
+    pub fn from_internal(locale: &InternalLocale) -> Self {
+        Self::new(locale.value())
+    }
+}
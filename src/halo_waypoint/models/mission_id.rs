@@ -1,7 +1,10 @@
 use scraper::ElementRef;
 use std::result::Result;
 
+use crate::chainable::Chainable;
 use crate::error::{Error, HaloWaypointError};
+use crate::games::game::GameId as InternalGameId;
+use crate::missions::dao::MissionsDao;
 use crate::missions::mission::MissionId as InternalMissionId;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
@@ -26,17 +29,14 @@ impl MissionId {
             .map_err(Error::HaloWaypoint)
     }
 
-    pub fn to_internal(&self) -> InternalMissionId {
-        let id = match self.0 {
-            0..=9 => self.0 + 1,
-            31..=43 => self.0 - 28,
-            70..=78 => self.0 - 68,
-            168..=175 => self.0 - 165,
-            178..=189 => self.0 - 177,
-            104..=111 => self.0 - 102,
-            other => unreachable!("External mission id shouldn't exist: {}", other),
-        };
-
-        InternalMissionId::new(id)
+    pub fn to_internal(
+        &self,
+        game_id: InternalGameId,
+        missions_dao: &dyn MissionsDao,
+    ) -> Result<InternalMissionId, Error> {
+        missions_dao
+            .find_by_game_id_and_halo_waypoint_id(game_id, self.0)
+            .map(|mission| mission.id())
+            .ok_or_else(|| HaloWaypointError::UnknownMissionId(self.0).pipe(Error::HaloWaypoint))
     }
 }
@@ -0,0 +1,25 @@
+use scraper::{ElementRef, Selector};
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct MissionTitle(String);
+
+impl MissionTitle {
+    pub fn new(title: &str) -> Self {
+        Self(title.to_string())
+    }
+
+    /// `None` when the page has no title, which only skips the cross-check with the catalogue.
+    pub fn from_halo_waypoint_service_record(element: ElementRef) -> Option<Self> {
+        let selector = Selector::parse(".mission-title").unwrap();
+
+        element
+            .select(&selector)
+            .next()
+            .map(|element| element.text().collect::<String>())
+            .map(|title| Self::new(title.trim()))
+    }
+
+    pub fn to_internal(&self) -> String {
+        self.0.clone()
+    }
+}
@@ -3,4 +3,7 @@ pub mod difficulty;
 pub mod fastest_time;
 pub mod game;
 pub mod highest_score;
+pub mod locale;
 pub mod mission_id;
+pub mod mission_title;
+pub mod platform;
@@ -0,0 +1,28 @@
+use crate::platforms::platform::Platform as InternalPlatform;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Platform {
+    XboxOne,
+    Pc,
+}
+
+impl Platform {
+    const XBOX_ONE: &'static str = "xbox-one";
+    const PC: &'static str = "pc";
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::XboxOne => Self::XBOX_ONE,
+            Self::Pc => Self::PC,
+        }
+        .to_string()
+    }
+
+    pub fn from_internal(platform: &InternalPlatform) -> Self {
+        match platform {
+            InternalPlatform::XboxOne => Self::XboxOne,
+            InternalPlatform::Pc => Self::Pc,
+        }
+    }
+}
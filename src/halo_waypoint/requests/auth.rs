@@ -5,37 +5,64 @@ use http::{Request, StatusCode};
 use hyper::Body;
 use regex::Regex;
 use std::convert::TryFrom;
-use std::env;
+use std::fmt;
 use std::result::Result;
 
 use crate::chainable::Chainable;
 use crate::error::{Error, HaloWaypointError};
+use crate::halo_waypoint::endpoints::{encode, Endpoints};
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub struct GetAuthRequest {
     login: String,
     password: String,
 }
 
-impl GetAuthRequest {
-    pub fn default() -> Self {
-        let login = env::var("HALO_WAYPOINT_LOGIN")
-            .expect("Environment variable not found: HALO_WAYPOINT_LOGIN");
-
-        let password = env::var("HALO_WAYPOINT_PASSWORD")
-            .expect("Environment variable not found: HALO_WAYPOINT_PASSWORD");
+impl fmt::Debug for GetAuthRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GetAuthRequest")
+            .field("login", &self.login)
+            .field("password", &"[redacted]")
+            .finish()
+    }
+}
 
-        Self { login, password }
+impl GetAuthRequest {
+    pub fn new(login: &str, password: &str) -> Self {
+        Self {
+            login: login.to_string(),
+            password: password.to_string(),
+        }
     }
 }
 
-pub struct GetAuthRequestGetForm;
+pub struct GetAuthRequestGetForm {
+    endpoints: Endpoints,
+}
 
 impl GetAuthRequestGetForm {
+    pub fn new(endpoints: &Endpoints) -> Self {
+        Self {
+            endpoints: endpoints.clone(),
+        }
+    }
+
+    fn to_uri(&self) -> String {
+        let state = format!("{}/en-us", self.endpoints.halo_waypoint_url());
+
+        format!(
+            "{}?client_id={}&scope=xbox.basic+xbox.offline_access&response_type=code&redirect_uri={}&locale=en-us&display=touch&state={}",
+            self.endpoints.authorize_url(),
+            encode(&self.endpoints.client_id()),
+            encode(&self.endpoints.redirect_uri()),
+            encode(&encode(&state)),
+        )
+    }
+
     fn to_request(&self) -> Request<Body> {
         Request::builder()
             .method(Method::GET)
-            .uri(Uri::from_static("https://login.live.com/oauth20_authorize.srf?client_id=000000004C0BD2F1&scope=xbox.basic+xbox.offline_access&response_type=code&redirect_uri=https:%2f%2fwww.halowaypoint.com%2fauth%2fcallback&locale=en-us&display=touch&state=https%253a%252f%252fwww.halowaypoint.com%252fen-us"))
+            .uri(self.to_uri())
             .body(Body::empty())
             .unwrap()
     }
@@ -192,11 +219,19 @@ impl From<&GetAuthRequestRedirect> for Request<Body> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct GetAuthResponse {
     auth_header: String,
 }
 
+impl fmt::Debug for GetAuthResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GetAuthResponse")
+            .field("auth_header", &"Auth=[redacted]")
+            .finish()
+    }
+}
+
 impl GetAuthResponse {
     fn regex_auth_header() -> Regex {
         Regex::new("^(Auth=[^;]+);").unwrap()
@@ -236,3 +271,40 @@ impl TryFrom<Response<String>> for GetAuthResponse {
         Self::try_from_response(res)
     }
 }
+
+#[cfg(test)]
+mod get_auth_request_get_form_test {
+    use super::*;
+
+    #[test]
+    fn to_uri() {
+        let endpoints = Endpoints::new(
+            "http://127.0.0.1:3001/oauth20_authorize.srf",
+            "000000004C0BD2F1",
+            "http://127.0.0.1:3001/auth/callback",
+            "http://127.0.0.1:3001",
+        );
+
+        assert_eq!(
+            GetAuthRequestGetForm::new(&endpoints).to_uri(),
+            "http://127.0.0.1:3001/oauth20_authorize.srf?client_id=000000004C0BD2F1&scope=xbox.basic+xbox.offline_access&response_type=code&redirect_uri=http%3a%2f%2f127.0.0.1%3a3001%2fauth%2fcallback&locale=en-us&display=touch&state=http%253a%252f%252f127.0.0.1%253a3001%252fen-us"
+        );
+    }
+}
+
+#[cfg(test)]
+mod get_auth_request_test {
+    use super::*;
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let req = GetAuthRequest::new("john", "117");
+        let res = GetAuthResponse::new("Auth=secret".to_string());
+
+        assert_eq!(
+            format!("{:?}", req),
+            "GetAuthRequest { login: \"john\", password: \"[redacted]\" }"
+        );
+        assert!(!format!("{:?}", res).contains("secret"));
+    }
+}
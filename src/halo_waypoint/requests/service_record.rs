@@ -1,88 +1,114 @@
 use http::method::Method;
-use http::uri::{Builder, PathAndQuery, Scheme, Uri};
+use http::uri::Uri;
 use http::{header, Request, Response, StatusCode};
 use hyper::Body;
 use scraper::{ElementRef, Html, Selector};
 use std::convert::TryFrom;
 use std::result::Result;
 use time::Time;
+use tracing::{debug, debug_span};
 
 use crate::campaign_modes::campaign_mode::CampaignMode as InternalCampaignMode;
 use crate::chainable::Chainable;
 use crate::difficulties::difficulty::Difficulty as InternalDifficulty;
 use crate::error::{Error, HaloWaypointError};
+use crate::gamertags::gamertag::Gamertag;
+use crate::games::dao::GamesDao;
 use crate::games::game::Game as InternalGame;
 use crate::games::game::GameId as InternalGameId;
+use crate::halo_waypoint::endpoints::{encode, Endpoints};
 use crate::halo_waypoint::models::campaign_mode::CampaignMode;
 use crate::halo_waypoint::models::difficulty::Difficulty;
 use crate::halo_waypoint::models::fastest_time::FastestTime;
 use crate::halo_waypoint::models::game::Game;
 use crate::halo_waypoint::models::highest_score::HighestScore;
+use crate::halo_waypoint::models::locale::Locale;
 use crate::halo_waypoint::models::mission_id::MissionId;
+use crate::halo_waypoint::models::mission_title::MissionTitle;
+use crate::halo_waypoint::models::platform::Platform;
 use crate::halo_waypoint::requests::auth::GetAuthResponse;
+use crate::locales::locale::Locale as InternalLocale;
+use crate::missions::dao::MissionsDao;
 use crate::missions::mission::MissionId as InternalMissionId;
+use crate::platforms::platform::Platform as InternalPlatform;
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct GetServiceRecordRequest {
-    player: String,
+    player: Gamertag,
     game: Game,
     campaign_mode: CampaignMode,
+    platform: Platform,
+    locale: Locale,
 }
 
 impl GetServiceRecordRequest {
-    pub fn new(player: &str, game: &Game, campaign_mode: &CampaignMode) -> Self {
+    pub fn new(
+        player: &Gamertag,
+        game: &Game,
+        campaign_mode: &CampaignMode,
+        platform: &Platform,
+        locale: &Locale,
+    ) -> Self {
         Self {
-            player: player.to_string(),
+            player: player.clone(),
             game: *game,
             campaign_mode: *campaign_mode,
+            platform: *platform,
+            locale: locale.clone(),
         }
     }
 
     pub fn from_internal(
-        player: &str,
+        player: &Gamertag,
         game: &InternalGame,
         campaign_mode: &InternalCampaignMode,
-    ) -> Self {
-        Self::new(
-            player,
-            &Game::from_internal(game),
-            &CampaignMode::from_internal(campaign_mode),
-        )
+        platform: &InternalPlatform,
+        locale: &InternalLocale,
+    ) -> Result<Self, Error> {
+        Game::from_internal(game).map(|game| {
+            Self::new(
+                player,
+                &game,
+                &CampaignMode::from_internal(campaign_mode),
+                &Platform::from_internal(platform),
+                &Locale::from_internal(locale),
+            )
+        })
     }
 }
 
 pub struct AuthenticatedGetServiceRecord {
+    endpoints: Endpoints,
     authentication: GetAuthResponse,
     request: GetServiceRecordRequest,
 }
 
 impl AuthenticatedGetServiceRecord {
-    pub fn new(authentication: GetAuthResponse, request: GetServiceRecordRequest) -> Self {
+    pub fn new(
+        endpoints: Endpoints,
+        authentication: GetAuthResponse,
+        request: GetServiceRecordRequest,
+    ) -> Self {
         Self {
+            endpoints,
             authentication,
             request,
         }
     }
 
     pub fn to_uri(&self) -> Uri {
-        let path_and_query = format!(
-            "/{}/games/{}/{}/service-records/players/{}/missions?game={}&campaignMode={}",
-            "en-us",                            // local
+        format!(
+            "{}/{}/games/{}/{}/service-records/players/{}/missions?game={}&campaignMode={}",
+            self.endpoints.halo_waypoint_url(),
+            self.request.locale.to_string(),
             "halo-the-master-chief-collection", // game
-            "xbox-one",                         // platform
-            self.request.player,
+            self.request.platform.to_string(),
+            encode(self.request.player.value()),
             self.request.game.to_string(),
             self.request.campaign_mode.to_string(),
         )
-        .pipe(PathAndQuery::from_maybe_shared)
-        .unwrap();
-
-        Builder::new()
-            .scheme(Scheme::HTTPS)
-            .authority("www.halowaypoint.com")
-            .path_and_query(path_and_query)
-            .build()
-            .unwrap()
+        .pipe(Uri::try_from)
+        .unwrap()
     }
 
     pub fn to_request(&self) -> Request<Body> {
@@ -109,17 +135,62 @@ mod get_service_record_request_test {
     #[test]
     fn to_uri() {
         let req = AuthenticatedGetServiceRecord::new(
+            Endpoints::new("", "", "", "https://www.halowaypoint.com"),
             GetAuthResponse::new("".to_string()),
-            GetServiceRecordRequest::new("John117", &Game::Halo, &CampaignMode::Solo),
+            GetServiceRecordRequest::new(
+                &Gamertag::try_from_str("John117").unwrap(),
+                &Game::Halo,
+                &CampaignMode::Solo,
+                &Platform::XboxOne,
+                &Locale::new("en-us"),
+            ),
         );
         let uri = req.to_uri();
 
+        assert_eq!(uri.host(), Some("www.halowaypoint.com"));
         assert_eq!(uri.path(), "/en-us/games/halo-the-master-chief-collection/xbox-one/service-records/players/John117/missions");
         assert_eq!(
             uri.query(),
             Some("game=HaloCombatEvolved&campaignMode=Solo")
         );
     }
+
+    #[test]
+    fn to_uri_with_encoded_player() {
+        let req = AuthenticatedGetServiceRecord::new(
+            Endpoints::new("", "", "", "https://www.halowaypoint.com"),
+            GetAuthResponse::new("".to_string()),
+            GetServiceRecordRequest::new(
+                &Gamertag::try_from_str("Master Chief").unwrap(),
+                &Game::Halo,
+                &CampaignMode::Solo,
+                &Platform::XboxOne,
+                &Locale::new("en-us"),
+            ),
+        );
+        let uri = req.to_uri();
+
+        assert_eq!(uri.path(), "/en-us/games/halo-the-master-chief-collection/xbox-one/service-records/players/Master%20Chief/missions");
+    }
+
+    #[test]
+    fn to_uri_with_platform_and_locale() {
+        let req = AuthenticatedGetServiceRecord::new(
+            Endpoints::new("", "", "", "https://www.halowaypoint.com"),
+            GetAuthResponse::new("".to_string()),
+            GetServiceRecordRequest::new(
+                &Gamertag::try_from_str("John117").unwrap(),
+                &Game::Halo3,
+                &CampaignMode::Coop,
+                &Platform::Pc,
+                &Locale::new("fr-fr"),
+            ),
+        );
+        let uri = req.to_uri();
+
+        assert_eq!(uri.path(), "/fr-fr/games/halo-the-master-chief-collection/pc/service-records/players/John117/missions");
+        assert_eq!(uri.query(), Some("game=Halo3&campaignMode=Coop"));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -131,16 +202,51 @@ pub struct GetServiceRecordResponse {
 
 impl GetServiceRecordResponse {
     fn try_from_response(res: Response<String>) -> Result<Self, Error> {
+        let span = debug_span!("parse", status = res.status().as_u16());
+        let _enter = span.enter();
+
         match res.status() {
-            StatusCode::OK => Html::parse_fragment(res.body())
+            StatusCode::OK => Self::try_from_html(res.body()),
+            // A 404 only means an unknown player when Waypoint's error page says so,
+            // a wrong URL or locale gets one too.
+            StatusCode::NOT_FOUND => Html::parse_fragment(res.body())
                 .root_element()
-                .pipe(Self::try_from_halo_waypoint_service_record),
+                .pipe(Self::unavailable_halo_waypoint_service_record)
+                .unwrap_or_else(|| HaloWaypointError::Http(res.into_body()))
+                .pipe(Error::HaloWaypoint)
+                .pipe(Err),
             _ => HaloWaypointError::Http(res.into_body())
                 .pipe(Error::HaloWaypoint)
                 .pipe(Err),
         }
     }
 
+    /// Parses the body of a missions page, such as one saved from a browser.
+    pub fn try_from_html(html: &str) -> Result<Self, Error> {
+        let html = Html::parse_fragment(html);
+        match Self::unavailable_halo_waypoint_service_record(html.root_element()) {
+            Some(err) => err.pipe(Error::HaloWaypoint).pipe(Err),
+            None => Self::try_from_halo_waypoint_service_record(html.root_element()),
+        }
+    }
+
+    /// Waypoint answers unknown gamertags and hidden stats with an error block in place of the
+    /// missions chart. Only a player that exists gets the game and mode dropdowns. The markup is
+    /// the same in every locale, unlike the messages.
+    fn unavailable_halo_waypoint_service_record(element: ElementRef) -> Option<HaloWaypointError> {
+        let error = Selector::parse("#halomasterchiefcollection-campaign-error").unwrap();
+        let dropdowns = Selector::parse("#halomasterchiefcollection-campaign-dropdowns").unwrap();
+        let missions = Selector::parse("[data-mission-id]").unwrap();
+
+        if element.select(&missions).next().is_some() || element.select(&error).next().is_none() {
+            None
+        } else if element.select(&dropdowns).next().is_some() {
+            Some(HaloWaypointError::PrivateServiceRecord)
+        } else {
+            Some(HaloWaypointError::PlayerNotFound)
+        }
+    }
+
     fn try_from_halo_waypoint_service_record(element: ElementRef) -> Result<Self, Error> {
         let game = Game::try_from_halo_waypoint_service_record(element);
         let campaign_mode = CampaignMode::try_from_halo_waypoint_service_record(element);
@@ -155,11 +261,15 @@ impl GetServiceRecordResponse {
             });
 
         match (game, campaign_mode, missions) {
-            (Ok(game), Ok(campaign_mode), Ok(missions)) => Ok(Self {
-                game,
-                campaign_mode,
-                missions,
-            }),
+            (Ok(game), Ok(campaign_mode), Ok(missions)) => {
+                debug!(game = ?game, campaign_mode = ?campaign_mode, missions = missions.len(), "parsed service record");
+
+                Ok(Self {
+                    game,
+                    campaign_mode,
+                    missions,
+                })
+            }
             (game, campaign_mode, missions) => {
                 vec![game.err(), campaign_mode.err(), missions.err()]
                     .into_iter()
@@ -171,36 +281,77 @@ impl GetServiceRecordResponse {
         }
     }
 
+    /// Unknown missions are reported as errors next to the mapped runs, a game missing from the
+    /// catalogue as the only error.
+    #[allow(clippy::type_complexity)]
     pub fn to_internal(
         &self,
-    ) -> Vec<(
-        InternalGameId,
-        InternalMissionId,
-        InternalCampaignMode,
-        InternalDifficulty,
-        Time,
-        i32,
-    )> {
-        let game_id = self.game.to_internal();
+        games_dao: &dyn GamesDao,
+        missions_dao: &dyn MissionsDao,
+    ) -> Vec<
+        Result<
+            (
+                InternalGameId,
+                InternalMissionId,
+                InternalCampaignMode,
+                InternalDifficulty,
+                Time,
+                i32,
+            ),
+            Error,
+        >,
+    > {
+        let game_id = match self.game.to_internal(games_dao) {
+            Ok(game_id) => game_id,
+            Err(err) => return vec![Err(err)],
+        };
         let campaign_mode = self.campaign_mode.to_internal();
 
         self.missions
             .iter()
             .filter_map(move |m| {
-                let mission_id = m.id.to_internal();
+                let mission_id = m.id.to_internal(game_id, missions_dao);
                 let difficulty = m.difficulty.to_internal();
                 let time = m.fastest_time.to_internal();
                 let score = m.highest_score.to_internal().unwrap_or(0);
 
-                match (difficulty, time) {
-                    (Some(difficulty), Some(time)) => {
-                        Some((game_id, mission_id, campaign_mode, difficulty, time, score))
-                    }
+                match (mission_id, difficulty, time) {
+                    (Err(err), _, _) => Some(Err(err)),
+                    (Ok(mission_id), Some(difficulty), Some(time)) => Some(Ok((
+                        game_id,
+                        mission_id,
+                        campaign_mode,
+                        difficulty,
+                        time,
+                        score,
+                    ))),
                     _ => None,
                 }
             })
             .collect()
     }
+
+    pub fn mission_titles(
+        &self,
+        games_dao: &dyn GamesDao,
+        missions_dao: &dyn MissionsDao,
+    ) -> Vec<(InternalGameId, InternalMissionId, String)> {
+        let game_id = match self.game.to_internal(games_dao) {
+            Ok(game_id) => game_id,
+            Err(_) => return vec![],
+        };
+
+        self.missions
+            .iter()
+            .filter_map(|m| {
+                let title = m.title.as_ref()?.to_internal();
+
+                m.id.to_internal(game_id, missions_dao)
+                    .ok()
+                    .map(|mission_id| (game_id, mission_id, title))
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<Response<String>> for GetServiceRecordResponse {
@@ -210,9 +361,10 @@ impl TryFrom<Response<String>> for GetServiceRecordResponse {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GetServiceRecordResponseMission {
     id: MissionId,
+    title: Option<MissionTitle>,
     difficulty: Difficulty,
     fastest_time: FastestTime,
     highest_score: HighestScore,
@@ -221,6 +373,7 @@ pub struct GetServiceRecordResponseMission {
 impl GetServiceRecordResponseMission {
     fn try_from_halo_waypoint_service_record(element: ElementRef) -> Result<Self, Error> {
         let id = MissionId::try_from_halo_waypoint_service_record(element);
+        let title = MissionTitle::from_halo_waypoint_service_record(element);
         let difficulty = Difficulty::try_from_halo_waypoint_service_record(element);
         let fastest_time = FastestTime::try_from_halo_waypoint_service_record(element);
         let highest_score = HighestScore::try_from_halo_waypoint_service_record(element);
@@ -228,6 +381,7 @@ impl GetServiceRecordResponseMission {
         match (id, difficulty, fastest_time, highest_score) {
             (Ok(id), Ok(difficulty), Ok(fastest_time), Ok(highest_score)) => Ok(Self {
                 id,
+                title,
                 difficulty,
                 fastest_time,
                 highest_score,
@@ -250,6 +404,7 @@ impl GetServiceRecordResponseMission {
 #[cfg(test)]
 mod get_service_record_response_test {
     use super::*;
+    use crate::catalogues::catalogue::Catalogue;
     use std::fs;
     use time::time;
 
@@ -270,6 +425,200 @@ mod get_service_record_response_test {
         assert_eq!(res.unwrap().len(), 12);
     }
 
+    #[test]
+    fn mission_titles_match_missions() {
+        let catalogue = Catalogue::default();
+        let missions_dao = catalogue.missions_dao();
+        let mismatches = fs::read_dir("resources/halo_waypoint/service_records/")
+            .unwrap()
+            .flat_map(|entry| {
+                fs::read_to_string(entry.unwrap().path())
+                    .unwrap()
+                    .pipe(|s| Html::parse_fragment(&s))
+                    .root_element()
+                    .pipe(GetServiceRecordResponse::try_from_halo_waypoint_service_record)
+                    .unwrap()
+                    .mission_titles(catalogue.games_dao(), missions_dao)
+            })
+            .filter(|(game_id, mission_id, title)| {
+                missions_dao
+                    .find_by_game_id_and_id(*game_id, *mission_id)
+                    .map_or(true, |mission| !mission.matches_name(title))
+            })
+            .collect::<Vec<(InternalGameId, InternalMissionId, String)>>();
+
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+    }
+
+    #[test]
+    fn to_internal_unknown_mission() {
+        let catalogue = Catalogue::default();
+        let mission = |id, title| GetServiceRecordResponseMission {
+            id: MissionId::new(id),
+            title: Some(MissionTitle::new(title)),
+            difficulty: Difficulty::Legendary,
+            fastest_time: FastestTime::Some(time!(00:15:53)),
+            highest_score: HighestScore::Some(23520),
+        };
+
+        let res = GetServiceRecordResponse {
+            game: Game::Halo,
+            campaign_mode: CampaignMode::Solo,
+            missions: vec![
+                mission(0, "THE PILLAR OF AUTUMN"),
+                mission(999, "NEW CONTENT"),
+            ],
+        }
+        .to_internal(catalogue.games_dao(), catalogue.missions_dao());
+
+        assert_eq!(res.len(), 2);
+        assert!(res[0].is_ok());
+        assert!(matches!(
+            res[1],
+            Err(Error::HaloWaypoint(HaloWaypointError::UnknownMissionId(
+                999
+            )))
+        ));
+    }
+
+    #[test]
+    fn to_internal_renumbered_game() {
+        let catalogue = Catalogue::try_from_str(
+            r#"
+            [[games]]
+            id = 1
+            name = "Halo 2"
+            short_code = "halo2"
+            release_year = 2004
+            developer = "Bungie"
+            halo_waypoint_key = "Halo2"
+            menu_order = 1
+
+            [[games]]
+            id = 20
+            name = "Halo"
+            short_code = "haloce"
+            release_year = 2001
+            developer = "Bungie"
+            halo_waypoint_key = "HaloCombatEvolved"
+            menu_order = 2
+
+            [[missions]]
+            game_id = 20
+            id = 7
+            name = "The Pillar of Autumn"
+            ordinal = 1
+            halo_waypoint_id = 0
+            "#,
+        )
+        .unwrap();
+        let res = fs::read_to_string("resources/halo_waypoint/service_records/halo_solo.html")
+            .unwrap()
+            .pipe(|s| Html::parse_fragment(&s))
+            .root_element()
+            .pipe(GetServiceRecordResponse::try_from_halo_waypoint_service_record)
+            .unwrap()
+            .to_internal(catalogue.games_dao(), catalogue.missions_dao());
+
+        assert!(matches!(
+            res.first(),
+            Some(Ok((game_id, mission_id, _, _, _, _)))
+                if *game_id == InternalGameId::new(20) && *mission_id == InternalMissionId::new(7)
+        ));
+    }
+
+    #[test]
+    fn missing_titles() {
+        let catalogue = Catalogue::default();
+        let missions_dao = catalogue.missions_dao();
+        let res = fs::read_to_string("resources/halo_waypoint/missing_titles/halo_solo.html")
+            .unwrap()
+            .pipe(|s| Html::parse_fragment(&s))
+            .root_element()
+            .pipe(GetServiceRecordResponse::try_from_halo_waypoint_service_record)
+            .unwrap();
+
+        let service_records = res.to_internal(catalogue.games_dao(), missions_dao);
+
+        assert!(res
+            .mission_titles(catalogue.games_dao(), missions_dao)
+            .is_empty());
+        assert_eq!(service_records.len(), 10);
+        assert!(service_records.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn player_not_found() {
+        let res = fs::read_to_string("resources/halo_waypoint/errors/player_not_found.html")
+            .unwrap()
+            .pipe(Response::new)
+            .pipe(GetServiceRecordResponse::try_from_response);
+
+        assert!(matches!(
+            res,
+            Err(Error::HaloWaypoint(HaloWaypointError::PlayerNotFound))
+        ));
+    }
+
+    #[test]
+    fn player_not_found_localized() {
+        let res = fs::read_to_string("resources/halo_waypoint/errors/player_not_found.html")
+            .unwrap()
+            .replace("Service Record Unavailable", "Service Record indisponible")
+            .replace(
+                "We couldn't find the player you were looking for. Check the gamertag and try again.",
+                "Nous n'avons pas trouvé ce joueur.",
+            )
+            .pipe(Response::new)
+            .pipe(GetServiceRecordResponse::try_from_response);
+
+        assert!(matches!(
+            res,
+            Err(Error::HaloWaypoint(HaloWaypointError::PlayerNotFound))
+        ));
+    }
+
+    #[test]
+    fn player_not_found_status() {
+        let res = fs::read_to_string("resources/halo_waypoint/errors/player_not_found.html")
+            .unwrap()
+            .pipe(|body| Response::builder().status(StatusCode::NOT_FOUND).body(body))
+            .unwrap()
+            .pipe(GetServiceRecordResponse::try_from_response);
+
+        assert!(matches!(
+            res,
+            Err(Error::HaloWaypoint(HaloWaypointError::PlayerNotFound))
+        ));
+    }
+
+    #[test]
+    fn not_found_status_without_error_page() {
+        let res = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body("<html><body>Page not found</body></html>".to_string())
+            .unwrap()
+            .pipe(GetServiceRecordResponse::try_from_response);
+
+        assert!(matches!(
+            res,
+            Err(Error::HaloWaypoint(HaloWaypointError::Http(_)))
+        ));
+    }
+
+    #[test]
+    fn private_service_record() {
+        let res = fs::read_to_string("resources/halo_waypoint/errors/private_service_record.html")
+            .unwrap()
+            .pipe(Response::new)
+            .pipe(GetServiceRecordResponse::try_from_response);
+
+        assert!(matches!(
+            res,
+            Err(Error::HaloWaypoint(HaloWaypointError::PrivateServiceRecord))
+        ));
+    }
+
     #[test]
     fn halo_solo() {
         let res = fs::read_to_string("resources/halo_waypoint/service_records/halo_solo.html")
@@ -286,6 +635,7 @@ mod get_service_record_response_test {
             res.missions.get(0),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(0),
+                title: Some(MissionTitle::new("THE PILLAR OF AUTUMN")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:15:53)),
                 highest_score: HighestScore::Some(23520),
@@ -296,6 +646,7 @@ mod get_service_record_response_test {
             res.missions.get(1),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(1),
+                title: Some(MissionTitle::new("HALO")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(01:27:34)),
                 highest_score: HighestScore::None,
@@ -306,6 +657,7 @@ mod get_service_record_response_test {
             res.missions.get(2),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(2),
+                title: Some(MissionTitle::new("THE TRUTH AND RECONCILIATION")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:39:03)),
                 highest_score: HighestScore::Some(6974),
@@ -316,6 +668,7 @@ mod get_service_record_response_test {
             res.missions.get(3),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(3),
+                title: Some(MissionTitle::new("THE SILENT CARTOGRAPHER")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:20:47)),
                 highest_score: HighestScore::Some(8204),
@@ -326,6 +679,7 @@ mod get_service_record_response_test {
             res.missions.get(4),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(4),
+                title: Some(MissionTitle::new("ASSAULT ON THE CONTROL ROOM")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:44:50)),
                 highest_score: HighestScore::Some(10301),
@@ -336,6 +690,7 @@ mod get_service_record_response_test {
             res.missions.get(5),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(5),
+                title: Some(MissionTitle::new("343 GUILTY SPARK")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:18:56)),
                 highest_score: HighestScore::Some(3601),
@@ -346,6 +701,7 @@ mod get_service_record_response_test {
             res.missions.get(6),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(6),
+                title: Some(MissionTitle::new("THE LIBRARY")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:41:19)),
                 highest_score: HighestScore::Some(11838),
@@ -356,6 +712,7 @@ mod get_service_record_response_test {
             res.missions.get(7),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(7),
+                title: Some(MissionTitle::new("TWO BETRAYALS")),
                 difficulty: Difficulty::None,
                 fastest_time: FastestTime::None,
                 highest_score: HighestScore::None
@@ -366,6 +723,7 @@ mod get_service_record_response_test {
             res.missions.get(8),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(8),
+                title: Some(MissionTitle::new("KEYES")),
                 difficulty: Difficulty::None,
                 fastest_time: FastestTime::None,
                 highest_score: HighestScore::None,
@@ -376,6 +734,7 @@ mod get_service_record_response_test {
             res.missions.get(9),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(9),
+                title: Some(MissionTitle::new("THE MAW")),
                 difficulty: Difficulty::Normal,
                 fastest_time: FastestTime::Some(time!(00:39:46)),
                 highest_score: HighestScore::Some(3319),
@@ -400,6 +759,7 @@ mod get_service_record_response_test {
             res.missions.get(0),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(0),
+                title: Some(MissionTitle::new("THE PILLAR OF AUTUMN")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:13:35)),
                 highest_score: HighestScore::Some(19147),
@@ -410,6 +770,7 @@ mod get_service_record_response_test {
             res.missions.get(1),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(1),
+                title: Some(MissionTitle::new("HALO")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:35:13)),
                 highest_score: HighestScore::Some(7953),
@@ -420,6 +781,7 @@ mod get_service_record_response_test {
             res.missions.get(2),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(2),
+                title: Some(MissionTitle::new("THE TRUTH AND RECONCILIATION")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:42:42)),
                 highest_score: HighestScore::Some(23553),
@@ -430,6 +792,7 @@ mod get_service_record_response_test {
             res.missions.get(3),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(3),
+                title: Some(MissionTitle::new("THE SILENT CARTOGRAPHER")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:27:46)),
                 highest_score: HighestScore::Some(17378),
@@ -440,6 +803,7 @@ mod get_service_record_response_test {
             res.missions.get(4),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(4),
+                title: Some(MissionTitle::new("ASSAULT ON THE CONTROL ROOM")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:23:57)),
                 highest_score: HighestScore::None,
@@ -450,6 +814,7 @@ mod get_service_record_response_test {
             res.missions.get(5),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(5),
+                title: Some(MissionTitle::new("343 GUILTY SPARK")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:29:31)),
                 highest_score: HighestScore::Some(11021),
@@ -460,6 +825,7 @@ mod get_service_record_response_test {
             res.missions.get(6),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(6),
+                title: Some(MissionTitle::new("THE LIBRARY")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:59:24)),
                 highest_score: HighestScore::Some(44636),
@@ -470,6 +836,7 @@ mod get_service_record_response_test {
             res.missions.get(7),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(7),
+                title: Some(MissionTitle::new("TWO BETRAYALS")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:57:49)),
                 highest_score: HighestScore::Some(12172),
@@ -480,6 +847,7 @@ mod get_service_record_response_test {
             res.missions.get(8),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(8),
+                title: Some(MissionTitle::new("KEYES")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:50:27)),
                 highest_score: HighestScore::Some(16359),
@@ -490,6 +858,7 @@ mod get_service_record_response_test {
             res.missions.get(9),
             Some(&GetServiceRecordResponseMission {
                 id: MissionId::new(9),
+                title: Some(MissionTitle::new("THE MAW")),
                 difficulty: Difficulty::Legendary,
                 fastest_time: FastestTime::Some(time!(00:40:42)),
                 highest_score: HighestScore::Some(21823),
@@ -0,0 +1,95 @@
+use hyper::{header, Body, Request, Response, StatusCode};
+use serde::Serialize;
+use std::sync::Arc;
+use time::{Format, OffsetDateTime};
+
+use crate::graphql::context::Context;
+use crate::halo_waypoint::health::{AuthHealth, ClientHealth};
+
+#[derive(Serialize)]
+struct Readiness {
+    ready: bool,
+    credentials_configured: bool,
+    auth: &'static str,
+    last_successful_fetch: Option<String>,
+}
+
+impl Readiness {
+    fn from_health(health: &ClientHealth) -> Self {
+        let auth = match health.auth() {
+            AuthHealth::Valid => "valid",
+            AuthHealth::Invalid => "invalid",
+            AuthHealth::Missing => "missing",
+        };
+
+        Self {
+            ready: health.is_ready(),
+            credentials_configured: health.credentials_configured(),
+            auth,
+            last_successful_fetch: health
+                .last_success()
+                .map(|time| OffsetDateTime::from(time).format(Format::Rfc3339)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Service {
+    context: Arc<Context>,
+}
+
+impl Service {
+    pub fn new(context: Arc<Context>) -> Self {
+        Self { context }
+    }
+
+    /// The process is up; upstream state is left to `/readyz`.
+    pub async fn liveness(
+        &self,
+        _req: Request<Body>,
+    ) -> Result<Response<Body>, hyper::error::Error> {
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from("ok"))
+            .unwrap())
+    }
+
+    pub async fn readiness(
+        &self,
+        _req: Request<Body>,
+    ) -> Result<Response<Body>, hyper::error::Error> {
+        let readiness = Readiness::from_health(&self.context.service_records_doa().health());
+        let status = if readiness.ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+
+        Ok(Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&readiness).unwrap()))
+            .unwrap())
+    }
+}
+
+#[cfg(test)]
+mod readiness_test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn from_health() {
+        let health = ClientHealth::new(
+            true,
+            AuthHealth::Valid,
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&Readiness::from_health(&health)).unwrap(),
+            r#"{"ready":true,"credentials_configured":true,"auth":"valid","last_successful_fetch":"2020-09-13T12:26:40+00:00"}"#
+        );
+    }
+}
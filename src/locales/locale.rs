@@ -0,0 +1,101 @@
+use itertools::Itertools;
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Locale(String);
+
+impl Locale {
+    /// Accepts `language-region` tags such as `en-us` or `FR-fr`, stored lowercase.
+    pub fn try_from_str(locale: &str) -> Option<Self> {
+        let locale = locale.trim().to_lowercase();
+        let mut parts = locale.split('-');
+
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(language), Some(region), None)
+                if language.len() == 2
+                    && region.len() == 2
+                    && locale.chars().all(|c| c.is_ascii_lowercase() || c == '-') =>
+            {
+                Some(Self(locale))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses an `Accept-Language` header, most preferred first. Bare languages such as `fr` are skipped.
+    pub fn from_accept_language(header: &str) -> Vec<Self> {
+        header
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let locale = parts.next().and_then(Self::try_from_str)?;
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                if quality > 0.0 {
+                    Some((locale, quality))
+                } else {
+                    None
+                }
+            })
+            .sorted_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal))
+            .map(|(locale, _)| locale)
+            .collect()
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self("en-us".to_string())
+    }
+}
+
+#[cfg(test)]
+mod locale_test {
+    use super::*;
+
+    #[test]
+    fn try_from_str_valid() {
+        assert_eq!(
+            Locale::try_from_str("fr-fr").map(|l| l.0),
+            Some("fr-fr".to_string())
+        );
+        assert_eq!(
+            Locale::try_from_str("DE-de").map(|l| l.0),
+            Some("de-de".to_string())
+        );
+    }
+
+    #[test]
+    fn try_from_str_invalid() {
+        assert_eq!(Locale::try_from_str(""), None);
+        assert_eq!(Locale::try_from_str("en"), None);
+        assert_eq!(Locale::try_from_str("en-us-x"), None);
+        assert_eq!(Locale::try_from_str("e1-us"), None);
+    }
+
+    #[test]
+    fn from_accept_language() {
+        let locales = Locale::from_accept_language("fr;q=0.9, de-DE;q=0.5, fr-FR, en-US;q=0")
+            .iter()
+            .map(|l| l.value().to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(locales, vec!["fr-fr", "de-de"]);
+    }
+
+    #[test]
+    fn language() {
+        assert_eq!(Locale::default().language(), "en");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::games::game::GameId;
+use crate::locales::locale::Locale;
+use crate::localisations::localisation::Localisation;
+use crate::missions::mission::MissionId;
+
+pub trait LocalisationsDao {
+    fn find_game_name(&self, game_id: GameId, locale: &Locale) -> Option<String>;
+    fn find_mission_name(&self, game_id: GameId, id: MissionId, locale: &Locale) -> Option<String>;
+}
+
+#[derive(Clone)]
+pub struct InMemoryLocalisationsDao {
+    localisations: Vec<Localisation>,
+}
+
+impl InMemoryLocalisationsDao {
+    const DEFAULTS: [(&'static str, &'static str); 2] = [
+        (
+            "fr-fr",
+            include_str!("../../resources/localisations/fr-fr.toml"),
+        ),
+        (
+            "de-de",
+            include_str!("../../resources/localisations/de-de.toml"),
+        ),
+    ];
+
+    pub fn new(localisations: Vec<Localisation>) -> Self {
+        Self { localisations }
+    }
+
+    fn find_by_locale(&self, locale: &Locale) -> Option<&Localisation> {
+        self.localisations
+            .iter()
+            .find(|localisation| localisation.locale() == locale)
+    }
+
+    pub fn default() -> Self {
+        let localisations = Self::DEFAULTS
+            .iter()
+            .map(|(locale, localisation)| {
+                let locale = Locale::try_from_str(locale).expect("Invalid default locale");
+                Localisation::try_from_str(locale, localisation)
+                    .expect("Invalid default localisation")
+            })
+            .collect();
+
+        Self::new(localisations)
+    }
+}
+
+impl LocalisationsDao for InMemoryLocalisationsDao {
+    fn find_game_name(&self, game_id: GameId, locale: &Locale) -> Option<String> {
+        self.find_by_locale(locale)
+            .and_then(|localisation| localisation.game_name(game_id))
+    }
+
+    fn find_mission_name(&self, game_id: GameId, id: MissionId, locale: &Locale) -> Option<String> {
+        self.find_by_locale(locale)
+            .and_then(|localisation| localisation.mission_name(game_id, id))
+    }
+}
+
+#[cfg(test)]
+mod in_memory_localisations_dao_test {
+    use super::*;
+
+    #[test]
+    fn find_mission_name() {
+        let dao = InMemoryLocalisationsDao::default();
+        let fr_fr = Locale::try_from_str("fr-fr").unwrap();
+
+        assert_eq!(
+            dao.find_mission_name(GameId::new(1), MissionId::new(7), &fr_fr),
+            Some("La Bibliothèque".to_string())
+        );
+        assert_eq!(
+            dao.find_mission_name(GameId::new(1), MissionId::new(7), &Locale::default()),
+            None
+        );
+    }
+}
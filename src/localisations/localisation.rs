@@ -0,0 +1,152 @@
+use serde::Deserialize;
+
+use crate::chainable::Chainable;
+use crate::error::{CatalogueError, Error};
+use crate::games::game::GameId;
+use crate::locales::locale::Locale;
+use crate::missions::mission::MissionId;
+
+#[derive(Deserialize)]
+struct LocalisationFile {
+    #[serde(default)]
+    games: Vec<LocalisationGame>,
+    #[serde(default)]
+    missions: Vec<LocalisationMission>,
+}
+
+#[derive(Deserialize)]
+struct LocalisationGame {
+    id: i32,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct LocalisationMission {
+    game_id: i32,
+    id: i32,
+    name: String,
+}
+
+/// Game and mission names for one locale, keyed by catalogue ids.
+#[derive(Clone)]
+pub struct Localisation {
+    locale: Locale,
+    game_names: Vec<(GameId, String)>,
+    mission_names: Vec<(GameId, MissionId, String)>,
+}
+
+impl Localisation {
+    pub fn try_from_str(locale: Locale, localisation: &str) -> Result<Self, Error> {
+        let file: LocalisationFile = toml::from_str(localisation)
+            .map_err(|err| CatalogueError::Parse(err.to_string()).pipe(Error::Catalogue))?;
+
+        let game_names = file
+            .games
+            .into_iter()
+            .map(|game| (GameId::new(game.id), game.name))
+            .collect();
+
+        let mission_names = file
+            .missions
+            .into_iter()
+            .map(|mission| {
+                (
+                    GameId::new(mission.game_id),
+                    MissionId::new(mission.id),
+                    mission.name,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            locale,
+            game_names,
+            mission_names,
+        })
+    }
+
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    pub fn game_name(&self, game_id: GameId) -> Option<String> {
+        self.game_names
+            .iter()
+            .find(|(g, _)| *g == game_id)
+            .map(|(_, name)| name.clone())
+    }
+
+    pub fn mission_name(&self, game_id: GameId, id: MissionId) -> Option<String> {
+        self.mission_names
+            .iter()
+            .find(|(g, m, _)| *g == game_id && *m == id)
+            .map(|(_, _, name)| name.clone())
+    }
+}
+
+#[cfg(test)]
+mod localisation_test {
+    use super::*;
+    use crate::catalogues::catalogue::Catalogue;
+    use crate::missions::dao::MissionsDao;
+    use std::fs;
+
+    #[test]
+    fn resources_match_catalogue() {
+        let catalogue = Catalogue::default();
+        let unknown = fs::read_dir("resources/localisations/")
+            .unwrap()
+            .flat_map(|entry| {
+                fs::read_to_string(entry.unwrap().path())
+                    .unwrap()
+                    .pipe(|s| Localisation::try_from_str(Locale::default(), &s))
+                    .unwrap()
+                    .mission_names
+            })
+            .filter(|(game_id, id, _)| {
+                catalogue
+                    .missions_dao()
+                    .find_by_game_id_and_id(*game_id, *id)
+                    .is_none()
+            })
+            .collect::<Vec<(GameId, MissionId, String)>>();
+
+        assert!(unknown.is_empty(), "{:?}", unknown);
+    }
+
+    #[test]
+    fn try_from_str() {
+        let localisation = Localisation::try_from_str(
+            Locale::try_from_str("fr-fr").unwrap(),
+            r#"
+            [[games]]
+            id = 5
+            name = "Halo : Reach"
+
+            [[missions]]
+            game_id = 1
+            id = 4
+            name = "Le Cartographe silencieux"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            localisation.game_name(GameId::new(5)),
+            Some("Halo : Reach".to_string())
+        );
+        assert_eq!(localisation.game_name(GameId::new(1)), None);
+        assert_eq!(
+            localisation.mission_name(GameId::new(1), MissionId::new(4)),
+            Some("Le Cartographe silencieux".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_toml() {
+        assert!(matches!(
+            Localisation::try_from_str(Locale::default(), "missions = "),
+            Err(Error::Catalogue(CatalogueError::Parse(_)))
+        ));
+    }
+}
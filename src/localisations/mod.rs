@@ -0,0 +1,2 @@
+pub mod dao;
+pub mod localisation;
@@ -1,28 +1,71 @@
 mod campaign_modes;
+mod catalogues;
 mod chainable;
+mod cli;
+mod compression;
+mod configs;
+mod cors;
 mod difficulties;
 mod error;
+mod gamertags;
 mod games;
 mod graphql;
 mod halo_waypoint;
+mod health;
+mod locales;
+mod localisations;
+mod metrics;
 mod missions;
+mod platforms;
+mod rest;
 mod service_records;
+mod world_records;
 
 use hyper::{
+    header,
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
 use std::net::SocketAddr;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use structopt::StructOpt;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::oneshot;
+use tracing::{error, info, info_span, warn};
+use tracing_futures::Instrument;
+use tracing_subscriber::EnvFilter;
 
+use crate::cli::command::Cli;
+use crate::compression::encoding::Encoding;
+use crate::configs::config::Config;
+use crate::error::Error;
 use crate::graphql::service::Service;
 
-fn addr() -> SocketAddr {
+fn addr(config: &Config) -> SocketAddr {
     let ip = [0, 0, 0, 0];
-    let port = std::env::var("PORT")
-        .map(|it| it.parse().unwrap())
-        .unwrap_or(3000);
 
-    SocketAddr::from((ip, port))
+    SocketAddr::from((ip, config.server().port()))
+}
+
+/// Logs every error of a list on its own line before giving up.
+fn exit(err: Error) -> ! {
+    match err {
+        Error::List(errs) => errs.iter().for_each(|err| error!("{}", err)),
+        err => error!("{}", err),
+    }
+
+    process::exit(1)
+}
+
+async fn shutdown_signal() {
+    let mut sigterm = signal(SignalKind::terminate()).expect("Failed to listen to SIGTERM");
+
+    tokio::select! {
+        _ = sigterm.recv() => info!(signal = "SIGTERM", "shutting down"),
+        _ = tokio::signal::ctrl_c() => info!(signal = "SIGINT", "shutting down"),
+    }
 }
 
 #[derive(Clone)]
@@ -40,37 +83,144 @@ impl NotFound {
     }
 }
 
+static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// JSON logs filtered by `RUST_LOG`: on stdout at `info` for the server, on stderr at `warn`
+/// for commands so their output can be piped.
+fn init_tracing(server: bool) {
+    let default = if server { "info" } else { "warn" };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default));
+    let subscriber = tracing_subscriber::fmt().json().with_env_filter(filter);
+
+    if server {
+        subscriber.init();
+    } else {
+        subscriber.with_writer(std::io::stderr).init();
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let graphql_service = Service::default();
+    let cli = Cli::from_args();
+    init_tracing(cli.command.is_none());
+
+    let config = Config::load().unwrap_or_else(|err| exit(err));
+    match cli.command {
+        Some(command) => command.run(&config).await.unwrap_or_else(|err| exit(err)),
+        None => serve(&config).await,
+    }
+}
+
+async fn serve(config: &Config) {
+    let graphql_service = Service::new(config).unwrap_or_else(|err| exit(err));
+    let context = graphql_service.context.clone();
+    let metrics_service = metrics::service::Service::default();
+    let health_service = health::service::Service::new(graphql_service.context.clone());
+    let rest_service = rest::service::Service::new(graphql_service.context.clone());
+    let cors_service = cors::service::Service::new(config.cors());
     let not_found_service = NotFound::default();
 
     let new_service = make_service_fn(move |_| {
         let graphql_service = graphql_service.clone();
+        let metrics_service = metrics_service.clone();
+        let health_service = health_service.clone();
+        let rest_service = rest_service.clone();
+        let cors_service = cors_service.clone();
         let not_found_service = not_found_service.clone();
 
         async {
             Ok::<_, hyper::Error>(service_fn(move |req| {
                 let graphql_service = graphql_service.clone();
+                let metrics_service = metrics_service.clone();
+                let health_service = health_service.clone();
+                let rest_service = rest_service.clone();
+                let cors_service = cors_service.clone();
                 let not_found_service = not_found_service.clone();
 
+                let span = info_span!(
+                    "request",
+                    id = REQUEST_ID.fetch_add(1, Ordering::Relaxed),
+                    method = %req.method(),
+                    path = %req.uri().path(),
+                );
+
                 async move {
-                    match (req.method(), req.uri().path()) {
+                    let start = Instant::now();
+                    let origin = req.headers().get(header::ORIGIN).cloned();
+                    let encoding = req
+                        .headers()
+                        .get(header::ACCEPT_ENCODING)
+                        .and_then(|value| value.to_str().ok())
+                        .map_or(Encoding::Identity, Encoding::negotiate);
+                    let res = match (req.method(), req.uri().path()) {
                         (&Method::GET, "/") => graphql_service.graphiql(req).await,
                         (&Method::GET, "/graphql") | (&Method::POST, "/graphql") => {
                             graphql_service.graphql(req).await
                         }
+                        (&Method::GET, "/metrics") => metrics_service.call(req).await,
+                        (&Method::GET, "/healthz") => health_service.liveness(req).await,
+                        (&Method::GET, "/readyz") => health_service.readiness(req).await,
+                        (&Method::GET, path) if path.starts_with("/api/") => {
+                            rest_service.call(req).await
+                        }
+                        (&Method::OPTIONS, path)
+                            if path == "/graphql" || path.starts_with("/api/") =>
+                        {
+                            cors_service.preflight(req).await
+                        }
                         _ => not_found_service.call(req).await,
+                    };
+                    let res = match res {
+                        Ok(mut res) => {
+                            cors_service.apply(origin.as_ref(), &mut res);
+                            encoding.compress(res).await
+                        }
+                        Err(err) => Err(err),
+                    };
+
+                    match &res {
+                        Ok(res) => info!(
+                            status = res.status().as_u16(),
+                            elapsed_ms = start.elapsed().as_millis() as u64,
+                            "request completed"
+                        ),
+                        Err(err) => error!(error = %err, "request failed"),
                     }
+
+                    res
                 }
+                .instrument(span)
             }))
         }
     });
 
-    let server = Server::bind(&addr()).serve(new_service);
-    println!("Listening on http://{}", server.local_addr());
+    let shutdown_timeout = config.server().shutdown_timeout();
+    let (shutdown, shutdown_started) = oneshot::channel::<()>();
+    let server = Server::bind(&addr(config)).serve(new_service);
+    info!(addr = %server.local_addr(), "listening");
 
-    if let Err(e) = server.await {
-        eprintln!("server error: {}", e)
+    // Stop accepting connections on the signal, then give in-flight requests until the deadline.
+    let server = server.with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        shutdown.send(()).ok();
+    });
+    let deadline = async move {
+        if shutdown_started.await.is_ok() {
+            tokio::time::delay_for(shutdown_timeout).await;
+        } else {
+            futures::future::pending::<()>().await;
+        }
+    };
+
+    tokio::select! {
+        res = server => {
+            if let Err(e) = res {
+                error!(error = %e, "server error")
+            }
+        }
+        _ = deadline => warn!("shutdown deadline exceeded, dropping in-flight requests"),
     }
+
+    context.service_records_doa().flush().await;
+    info!("stopped");
 }
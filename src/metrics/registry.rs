@@ -0,0 +1,131 @@
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, Encoder, HistogramVec, IntCounterVec,
+    TextEncoder,
+};
+
+use crate::error::Error;
+
+lazy_static! {
+    pub static ref UPSTREAM_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "halo_waypoint_requests_total",
+        "Requests sent to Halo Waypoint and the Microsoft login.",
+        &["endpoint", "status"]
+    )
+    .unwrap();
+    pub static ref UPSTREAM_LATENCY: HistogramVec = register_histogram_vec!(
+        "halo_waypoint_request_duration_seconds",
+        "Latency of requests sent to Halo Waypoint and the Microsoft login.",
+        &["endpoint"]
+    )
+    .unwrap();
+    pub static ref LOGINS: IntCounterVec = register_int_counter_vec!(
+        "halo_waypoint_logins_total",
+        "Halo Waypoint logins, cached authentications excluded.",
+        &["result"]
+    )
+    .unwrap();
+    pub static ref CACHE_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "cache_requests_total",
+        "Lookups in the Halo Waypoint client caches.",
+        &["cache", "result"]
+    )
+    .unwrap();
+    pub static ref CACHE_EVICTIONS: IntCounterVec = register_int_counter_vec!(
+        "cache_evictions_total",
+        "Live entries dropped because a Halo Waypoint client cache was full.",
+        &["cache"]
+    )
+    .unwrap();
+    pub static ref PARSE_FAILURES: IntCounterVec = register_int_counter_vec!(
+        "halo_waypoint_parse_failures_total",
+        "Halo Waypoint responses that could not be parsed, by error.",
+        &["error"]
+    )
+    .unwrap();
+    pub static ref GRAPHQL_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "graphql_requests_total",
+        "GraphQL requests by operation name and HTTP status.",
+        &["operation", "status"]
+    )
+    .unwrap();
+    pub static ref GRAPHQL_LATENCY: HistogramVec = register_histogram_vec!(
+        "graphql_request_duration_seconds",
+        "Latency of GraphQL requests by operation name and HTTP status.",
+        &["operation", "status"]
+    )
+    .unwrap();
+}
+
+/// Counts pages that didn't parse, a list counting once per error it holds.
+///
+/// HTTP errors, unknown players and private service records are answers, not parse failures.
+pub fn record_parse_failure(err: &Error) {
+    match err {
+        Error::HaloWaypoint(err) if err.is_parse_failure() => {
+            PARSE_FAILURES.with_label_values(&[err.into()]).inc()
+        }
+        Error::List(errs) => errs.iter().for_each(record_parse_failure),
+        _ => {}
+    }
+}
+
+pub fn encode() -> (String, Vec<u8>) {
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    encoder.encode(&prometheus::gather(), &mut buffer).unwrap();
+
+    (encoder.format_type().to_string(), buffer)
+}
+
+#[cfg(test)]
+mod registry_test {
+    use super::*;
+    use crate::error::HaloWaypointError;
+
+    #[test]
+    fn record_parse_failure_by_variant() {
+        let before = PARSE_FAILURES
+            .with_label_values(&["MissingDifficulty"])
+            .get();
+
+        record_parse_failure(&Error::List(vec![
+            Error::HaloWaypoint(HaloWaypointError::MissingDifficulty),
+            Error::HaloWaypoint(HaloWaypointError::MissingDifficulty),
+        ]));
+
+        assert_eq!(
+            PARSE_FAILURES
+                .with_label_values(&["MissingDifficulty"])
+                .get(),
+            before + 2
+        );
+    }
+
+    #[test]
+    fn record_parse_failure_ignores_answers() {
+        let before = PARSE_FAILURES.with_label_values(&["PlayerNotFound"]).get();
+
+        record_parse_failure(&Error::HaloWaypoint(HaloWaypointError::PlayerNotFound));
+        record_parse_failure(&Error::HaloWaypoint(HaloWaypointError::Http(
+            "<html></html>".to_string(),
+        )));
+
+        assert_eq!(
+            PARSE_FAILURES.with_label_values(&["PlayerNotFound"]).get(),
+            before
+        );
+        assert_eq!(PARSE_FAILURES.with_label_values(&["Http"]).get(), 0);
+    }
+
+    #[test]
+    fn encode_text_format() {
+        GRAPHQL_REQUESTS.with_label_values(&["games", "200"]).inc();
+
+        let (content_type, body) = encode();
+        let body = String::from_utf8(body).unwrap();
+
+        assert!(content_type.starts_with("text/plain"));
+        assert!(body.contains("graphql_requests_total{operation=\"games\",status=\"200\"}"));
+    }
+}
@@ -0,0 +1,22 @@
+use hyper::{header, Body, Request, Response, StatusCode};
+
+use crate::metrics::registry;
+
+#[derive(Clone)]
+pub struct Service;
+
+impl Service {
+    pub async fn call(&self, _req: Request<Body>) -> Result<Response<Body>, hyper::error::Error> {
+        let (content_type, body) = registry::encode();
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap())
+    }
+
+    pub fn default() -> Self {
+        Self
+    }
+}
@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
-use time::Time;
+use strum_macros::IntoStaticStr;
+use time::{Date, OffsetDateTime, Time};
 
 use crate::games::game::GameId;
+use crate::missions::par::Par;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct MissionId(i32);
@@ -28,13 +30,22 @@ impl PartialOrd for MissionId {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoStaticStr, juniper::GraphQLEnum)]
+#[strum(serialize_all = "lowercase")]
+pub enum MissionKind {
+    Level,
+    Cutscene,
+    Bonus,
+}
+
 #[derive(Clone)]
 pub struct Mission {
     game_id: GameId,
     id: MissionId,
     name: String,
-    par_time: Option<Time>,
-    par_score: Option<i32>,
+    pars: Vec<Par>,
+    kind: MissionKind,
+    ordinal: i32,
 }
 
 impl Mission {
@@ -42,15 +53,20 @@ impl Mission {
         game_id: GameId,
         id: MissionId,
         name: &str,
-        par_time: Option<Time>,
-        par_score: Option<i32>,
+        pars: Vec<Par>,
+        kind: MissionKind,
+        ordinal: i32,
     ) -> Self {
+        let mut pars = pars;
+        pars.sort_by_key(|par| par.effective_from());
+
         Self {
             game_id,
             id,
             name: name.to_string(),
-            par_time,
-            par_score,
+            pars,
+            kind,
+            ordinal,
         }
     }
 
@@ -67,10 +83,128 @@ impl Mission {
     }
 
     pub fn par_time(&self) -> Option<Time> {
-        self.par_time
+        self.current_par().and_then(|par| par.time())
     }
 
     pub fn par_score(&self) -> Option<i32> {
-        self.par_score
+        self.current_par().and_then(|par| par.score())
+    }
+
+    /// Every par version, oldest first.
+    pub fn pars(&self) -> Vec<Par> {
+        self.pars.clone()
+    }
+
+    pub fn current_par(&self) -> Option<Par> {
+        self.par_as_of(OffsetDateTime::now_utc().date())
+    }
+
+    /// The par in effect on `date`, for judging runs against the values of their time.
+    pub fn par_as_of(&self, date: Date) -> Option<Par> {
+        self.pars
+            .iter()
+            .rev()
+            .find(|par| par.effective_from() <= date)
+            .cloned()
+    }
+
+    pub fn par_by_version(&self, version: &str) -> Option<Par> {
+        self.pars
+            .iter()
+            .find(|par| par.version() == version)
+            .cloned()
+    }
+
+    pub fn kind(&self) -> MissionKind {
+        self.kind
+    }
+
+    pub fn ordinal(&self) -> i32 {
+        self.ordinal
+    }
+
+    /// Cutscenes can be "completed" on Waypoint but don't count towards a campaign.
+    pub fn is_playable(&self) -> bool {
+        self.kind != MissionKind::Cutscene
+    }
+
+    /// Compares names ignoring case, punctuation and a leading "The".
+    pub fn matches_name(&self, name: &str) -> bool {
+        Self::names_match(&self.name, name)
+    }
+
+    pub fn names_match(expected: &str, actual: &str) -> bool {
+        Self::normalize_name(expected) == Self::normalize_name(actual)
+    }
+
+    fn normalize_name(name: &str) -> String {
+        let name = name
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+            .collect::<String>();
+
+        let words = name.split_whitespace().collect::<Vec<&str>>();
+        match words.as_slice() {
+            ["the", rest @ ..] if !rest.is_empty() => rest.join(" "),
+            words => words.join(" "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod mission_test {
+    use super::*;
+    use time::date;
+
+    fn mission(name: &str) -> Mission {
+        Mission::new(
+            GameId::new(1),
+            MissionId::new(1),
+            name,
+            vec![],
+            MissionKind::Level,
+            1,
+        )
+    }
+
+    #[test]
+    fn matches_name() {
+        assert!(mission("Pillar of Autumn").matches_name("THE PILLAR OF AUTUMN"));
+        assert!(mission("Crow’s Nest").matches_name("CROW'S NEST"));
+        assert!(mission("ONI Sword Base").matches_name("ONI: SWORD BASE"));
+        assert!(mission("The Ark").matches_name("THE ARK"));
+    }
+
+    #[test]
+    fn matches_name_mismatch() {
+        assert!(!mission("Tvaso Highway").matches_name("TSAVO HIGHWAY"));
+        assert!(!mission("Halo").matches_name("THE MAW"));
+    }
+
+    #[test]
+    fn par_as_of() {
+        let launch = Par::new("launch", date!(2014 - 11 - 11), None, Some(17_000));
+        let patch = Par::new("patch", date!(2015 - 06 - 01), None, Some(15_000));
+        let mission = Mission::new(
+            GameId::new(1),
+            MissionId::new(1),
+            "Pillar of Autumn",
+            vec![patch.clone(), launch.clone()],
+            MissionKind::Level,
+            1,
+        );
+
+        assert_eq!(mission.par_as_of(date!(2014 - 11 - 10)), None);
+        assert_eq!(
+            mission.par_as_of(date!(2015 - 01 - 01)),
+            Some(launch.clone())
+        );
+        assert_eq!(
+            mission.par_as_of(date!(2015 - 06 - 01)),
+            Some(patch.clone())
+        );
+        assert_eq!(mission.par_by_version("launch"), Some(launch));
+        assert_eq!(mission.par_score(), Some(15_000));
     }
 }
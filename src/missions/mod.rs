@@ -1,2 +1,3 @@
 pub mod dao;
 pub mod mission;
+pub mod par;
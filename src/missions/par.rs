@@ -0,0 +1,42 @@
+use time::{Date, Time};
+
+/// Par values for a mission under one catalogue par version.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Par {
+    version: String,
+    effective_from: Date,
+    time: Option<Time>,
+    score: Option<i32>,
+}
+
+impl Par {
+    pub fn new(
+        version: &str,
+        effective_from: Date,
+        time: Option<Time>,
+        score: Option<i32>,
+    ) -> Self {
+        Self {
+            version: version.to_string(),
+            effective_from,
+            time,
+            score,
+        }
+    }
+
+    pub fn version(&self) -> String {
+        self.version.clone()
+    }
+
+    pub fn effective_from(&self) -> Date {
+        self.effective_from
+    }
+
+    pub fn time(&self) -> Option<Time> {
+        self.time
+    }
+
+    pub fn score(&self) -> Option<i32> {
+        self.score
+    }
+}
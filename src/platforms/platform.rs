@@ -0,0 +1,11 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Platform {
+    XboxOne,
+    Pc,
+}
+
+impl Default for Platform {
+    fn default() -> Self {
+        Self::XboxOne
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+
+use crate::games::game::Game;
+use crate::missions::mission::Mission;
+use crate::service_records::service_record::{ServiceRecord, ServiceRecordRun};
+
+/// Same unit as the GraphQL `timeInSeconds` fields.
+fn seconds(time: &time::Time) -> i32 {
+    (time.hour() as i32 * 60 + time.minute() as i32) * 60 + time.second() as i32
+}
+
+#[derive(Serialize)]
+pub struct GameResource {
+    id: i32,
+    name: String,
+    short_code: String,
+    release_year: i32,
+    developer: String,
+    mission_count: i32,
+    menu_order: i32,
+}
+
+impl GameResource {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            id: *game.id().value(),
+            name: game.name(),
+            short_code: game.short_code(),
+            release_year: game.release_year(),
+            developer: game.developer(),
+            mission_count: game.mission_count(),
+            menu_order: game.menu_order(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MissionResource {
+    id: i32,
+    name: String,
+    kind: &'static str,
+    ordinal: i32,
+    playable: bool,
+    par_time_in_seconds: Option<i32>,
+    par_score: Option<i32>,
+}
+
+impl MissionResource {
+    pub fn new(mission: &Mission) -> Self {
+        Self {
+            id: *mission.id().value(),
+            name: mission.name(),
+            kind: mission.kind().into(),
+            ordinal: mission.ordinal(),
+            playable: mission.is_playable(),
+            par_time_in_seconds: mission.par_time().as_ref().map(seconds),
+            par_score: mission.par_score(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ServiceRecordResource {
+    player: String,
+    game_id: i32,
+    mission_id: i32,
+    runs: Vec<RunResource>,
+}
+
+impl ServiceRecordResource {
+    pub fn new(service_record: &ServiceRecord) -> Self {
+        Self {
+            player: service_record.player().value().to_string(),
+            game_id: *service_record.game_id().value(),
+            mission_id: *service_record.mission_id().value(),
+            runs: service_record.runs().iter().map(RunResource::new).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RunResource {
+    campaign_mode: &'static str,
+    difficulty: &'static str,
+    time_in_seconds: i32,
+    score: i32,
+}
+
+impl RunResource {
+    pub fn new(run: &ServiceRecordRun) -> Self {
+        Self {
+            campaign_mode: run.campaign_mode().into(),
+            difficulty: run.difficulty().into(),
+            time_in_seconds: seconds(&run.time()),
+            score: run.score(),
+        }
+    }
+}
+
+/// Body of every non-2xx answer.
+#[derive(Serialize)]
+pub struct ErrorResource {
+    error: &'static str,
+    message: String,
+}
+
+impl ErrorResource {
+    pub fn new(error: &'static str, message: &str) -> Self {
+        Self {
+            error,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod resource_test {
+    use super::*;
+    use crate::campaign_modes::campaign_mode::CampaignMode;
+    use crate::difficulties::difficulty::Difficulty;
+    use crate::gamertags::gamertag::Gamertag;
+    use crate::games::game::GameId;
+    use crate::missions::mission::MissionId;
+    use time::time;
+
+    #[test]
+    fn service_record() {
+        let service_record = ServiceRecord::new(
+            Gamertag::try_from_str("Master Chief").unwrap(),
+            GameId::new(1),
+            MissionId::new(2),
+            vec![ServiceRecordRun::new(
+                GameId::new(1),
+                MissionId::new(2),
+                CampaignMode::Solo,
+                Difficulty::Legendary,
+                time!(01:15:53),
+                23520,
+            )],
+        );
+
+        assert_eq!(
+            serde_json::to_string(&ServiceRecordResource::new(&service_record)).unwrap(),
+            r#"{"player":"Master Chief","game_id":1,"mission_id":2,"runs":[{"campaign_mode":"solo","difficulty":"legendary","time_in_seconds":4553,"score":23520}]}"#
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, Request, Response, StatusCode};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::chainable::Chainable;
+use crate::cli::format::Format;
+use crate::error::{Error, HaloWaypointError};
+use crate::gamertags::gamertag::Gamertag;
+use crate::games::game::{Game, GameId};
+use crate::graphql::context::Context;
+use crate::locales::locale::Locale;
+use crate::missions::mission::MissionId;
+use crate::platforms::platform::Platform;
+use crate::rest::resource::{ErrorResource, GameResource, MissionResource, ServiceRecordResource};
+use crate::service_records::service_record::ServiceRecord;
+
+/// Read-only JSON API over the same DAOs as the GraphQL schema, described by `OPENAPI`.
+#[derive(Clone)]
+pub struct Service {
+    context: Arc<Context>,
+}
+
+impl Service {
+    pub const OPENAPI: &'static str = include_str!("../../resources/openapi.json");
+
+    pub fn new(context: Arc<Context>) -> Self {
+        Self { context }
+    }
+
+    pub async fn call(&self, req: Request<Body>) -> Result<Response<Body>, hyper::error::Error> {
+        let path = req.uri().path().trim_end_matches('/').to_string();
+        let segments = path.split('/').skip(1).collect::<Vec<&str>>();
+        let query = query(&req);
+
+        let res = match segments.as_slice() {
+            ["api", "openapi.json"] => Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(Self::OPENAPI))
+                .unwrap()),
+            ["api", "games"] => self.games(),
+            ["api", "games", game_id, "missions"] => self.missions(game_id),
+            ["api", "players", player, "export.csv"] => self.export(player, &query).await,
+            ["api", "players", player, "games", game_id] => {
+                self.service_records(player, game_id, &query).await
+            }
+            ["api", "players", player, "games", game_id, "missions", mission_id] => {
+                self.service_record(player, game_id, mission_id, &query)
+                    .await
+            }
+            _ => Err(not_found("Unknown route")),
+        };
+
+        Ok(res.unwrap_or_else(|res| res))
+    }
+
+    fn games(&self) -> Result<Response<Body>, Response<Body>> {
+        let games = self
+            .context
+            .games_dao()
+            .all()
+            .iter()
+            .map(GameResource::new)
+            .collect::<Vec<GameResource>>();
+
+        Ok(json(StatusCode::OK, &games))
+    }
+
+    fn missions(&self, game_id: &str) -> Result<Response<Body>, Response<Body>> {
+        let game = self.game(game_id)?;
+        let missions = self
+            .context
+            .missions_dao()
+            .all_by_game_id(game.id())
+            .iter()
+            .map(MissionResource::new)
+            .collect::<Vec<MissionResource>>();
+
+        Ok(json(StatusCode::OK, &missions))
+    }
+
+    async fn service_records(
+        &self,
+        player: &str,
+        game_id: &str,
+        query: &HashMap<String, String>,
+    ) -> Result<Response<Body>, Response<Body>> {
+        let player = gamertag(player)?;
+        let game = self.game(game_id)?;
+        let (platform, locale) = platform_and_locale(query)?;
+
+        let fetched = self
+            .context
+            .service_records_doa()
+            .find_by_player_and_game(player, game, platform, locale)
+            .await
+            .map_err(|err| error(&err))?;
+        let service_records = fetched
+            .records()
+            .iter()
+            .map(ServiceRecordResource::new)
+            .collect::<Vec<ServiceRecordResource>>();
+
+        Ok(json(StatusCode::OK, &service_records)
+            .pipe(|res| with_warnings(res, fetched.inconsistencies())))
+    }
+
+    /// A mission the player never finished answers an empty list of runs.
+    async fn service_record(
+        &self,
+        player: &str,
+        game_id: &str,
+        mission_id: &str,
+        query: &HashMap<String, String>,
+    ) -> Result<Response<Body>, Response<Body>> {
+        let player = gamertag(player)?;
+        let game = self.game(game_id)?;
+        let mission = mission_id
+            .parse()
+            .ok()
+            .map(MissionId::new)
+            .and_then(|id| {
+                self.context
+                    .missions_dao()
+                    .find_by_game_id_and_id(game.id(), id)
+            })
+            .ok_or_else(|| not_found("Unknown mission"))?;
+        let (platform, locale) = platform_and_locale(query)?;
+
+        let fetched = self
+            .context
+            .service_records_doa()
+            .find_by_player_and_mission(player.clone(), &mission, platform, locale)
+            .await
+            .map_err(|err| error(&err))?;
+        let warnings = fetched.inconsistencies().to_vec();
+        let service_record = fetched
+            .into_records()
+            .unwrap_or_else(|| ServiceRecord::new(player, game.id(), mission.id(), vec![]));
+
+        Ok(
+            json(StatusCode::OK, &ServiceRecordResource::new(&service_record))
+                .pipe(|res| with_warnings(res, &warnings)),
+        )
+    }
+
+    /// Every game unless `gameId` narrows it down, served as a download.
+    async fn export(
+        &self,
+        player: &str,
+        query: &HashMap<String, String>,
+    ) -> Result<Response<Body>, Response<Body>> {
+        let player = gamertag(player)?;
+        let games = match query.get("gameId") {
+            Some(game_id) => vec![self.game(game_id)?],
+            None => self.context.games_dao().all(),
+        };
+        let (platform, locale) = platform_and_locale(query)?;
+
+        let fetched = self
+            .context
+            .service_records_doa()
+            .export_by_player(player.clone(), games, platform, locale)
+            .await
+            .map_err(|err| error(&err))?;
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+            .header(
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.csv\"", player.value()),
+            )
+            .body(Body::from(Format::Csv.render(fetched.records())))
+            .unwrap()
+            .pipe(|res| with_warnings(res, fetched.inconsistencies())))
+    }
+
+    fn game(&self, game_id: &str) -> Result<Game, Response<Body>> {
+        game_id
+            .parse()
+            .ok()
+            .map(GameId::new)
+            .and_then(|id| self.context.games_dao().find_by_id(id))
+            .ok_or_else(|| not_found("Unknown game"))
+    }
+}
+
+fn json<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(body).unwrap()))
+        .unwrap()
+}
+
+fn not_found(message: &str) -> Response<Body> {
+    json(
+        StatusCode::NOT_FOUND,
+        &ErrorResource::new("not_found", message),
+    )
+}
+
+fn bad_request(message: &str) -> Response<Body> {
+    json(
+        StatusCode::BAD_REQUEST,
+        &ErrorResource::new("bad_request", message),
+    )
+}
+
+/// Upstream failures are the gateway's fault, not the client's.
+fn status(err: &Error) -> (StatusCode, &'static str) {
+    match err {
+        Error::InvalidArgument(_) | Error::InvalidGamertag(_) => {
+            (StatusCode::BAD_REQUEST, "bad_request")
+        }
+        Error::HaloWaypoint(HaloWaypointError::PlayerNotFound) => {
+            (StatusCode::NOT_FOUND, "player_not_found")
+        }
+        Error::HaloWaypoint(HaloWaypointError::PrivateServiceRecord) => {
+            (StatusCode::FORBIDDEN, "private_service_record")
+        }
+        Error::Config(_) => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+        _ => (StatusCode::BAD_GATEWAY, "bad_gateway"),
+    }
+}
+
+fn error(err: &Error) -> Response<Body> {
+    let (status, code) = status(err);
+
+    json(status, &ErrorResource::new(code, &err.to_string()))
+}
+
+/// One `Warning: 199` per inconsistency in the Halo Waypoint pages, the body leaves those missions out.
+fn with_warnings(mut res: Response<Body>, warnings: &[Error]) -> Response<Body> {
+    warnings
+        .iter()
+        .map(Error::to_string)
+        .unique()
+        .map(|message| {
+            let message = message.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("199 - \"{}\"", message)
+        })
+        .flat_map(|warning| HeaderValue::from_bytes(warning.as_bytes()))
+        .for_each(|warning| {
+            res.headers_mut().append(header::WARNING, warning);
+        });
+
+    res
+}
+
+fn gamertag(player: &str) -> Result<Gamertag, Response<Body>> {
+    decode(player)
+        .ok_or_else(|| bad_request("Invalid percent-encoding"))
+        .and_then(|player| Gamertag::try_from_str(&player).map_err(|err| error(&err)))
+}
+
+fn platform_and_locale(
+    query: &HashMap<String, String>,
+) -> Result<(Platform, Locale), Response<Body>> {
+    let platform = match query.get("platform").map(String::as_str) {
+        None => Platform::default(),
+        Some("xbox-one") => Platform::XboxOne,
+        Some("pc") => Platform::Pc,
+        Some(_) => return Err(bad_request("Invalid platform, expected xbox-one or pc")),
+    };
+    let locale = match query.get("locale") {
+        None => Locale::default(),
+        Some(locale) => {
+            Locale::try_from_str(locale).ok_or_else(|| bad_request("Invalid locale"))?
+        }
+    };
+
+    Ok((platform, locale))
+}
+
+fn query(req: &Request<Body>) -> HashMap<String, String> {
+    req.uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| {
+            let mut pair = pair.splitn(2, '=');
+            match (pair.next(), pair.next().and_then(decode)) {
+                (Some(key), Some(value)) => Some((key.to_string(), value)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Reverses percent-encoding, `None` when the escapes aren't valid UTF-8.
+fn decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = value.get(i + 1..i + 3)?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod service_test {
+    use super::*;
+    use crate::configs::config::Config;
+    use serde_json::{json, Value};
+
+    async fn get(uri: &str) -> (StatusCode, Value) {
+        let service = Context::new(&Config::default())
+            .map(Arc::new)
+            .map(Service::new)
+            .unwrap();
+        let res = Request::get(uri)
+            .body(Body::empty())
+            .unwrap()
+            .pipe(|req| service.call(req))
+            .await
+            .unwrap();
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn games() {
+        let (status, body) = get("/api/games").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.as_array().unwrap().iter().any(|game| game["id"] == 1));
+    }
+
+    #[tokio::test]
+    async fn unknown_game() {
+        assert_eq!(
+            get("/api/games/999/missions").await,
+            (
+                StatusCode::NOT_FOUND,
+                json!({ "error": "not_found", "message": "Unknown game" })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_mission() {
+        assert_eq!(
+            get("/api/players/John117/games/1/missions/999").await,
+            (
+                StatusCode::NOT_FOUND,
+                json!({ "error": "not_found", "message": "Unknown mission" })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_route() {
+        assert_eq!(
+            get("/api/players").await,
+            (
+                StatusCode::NOT_FOUND,
+                json!({ "error": "not_found", "message": "Unknown route" })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_platform() {
+        assert_eq!(
+            get("/api/players/John117/games/1?platform=ps5").await,
+            (
+                StatusCode::BAD_REQUEST,
+                json!({
+                    "error": "bad_request",
+                    "message": "Invalid platform, expected xbox-one or pc"
+                })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_locale() {
+        assert_eq!(
+            get("/api/players/John117/export.csv?locale=english").await,
+            (
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "bad_request", "message": "Invalid locale" })
+            )
+        );
+    }
+
+    #[test]
+    fn decode_gamertag() {
+        assert_eq!(decode("Master%20Chief"), Some("Master Chief".to_string()));
+        assert_eq!(decode("John117"), Some("John117".to_string()));
+        assert_eq!(decode("John%2"), None);
+    }
+
+    #[test]
+    fn status_by_error() {
+        assert_eq!(
+            status(&Error::HaloWaypoint(HaloWaypointError::PlayerNotFound)).0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(&Error::Hyper("timeout".to_string())).0,
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            status(&Error::InvalidGamertag("".to_string())).0,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn warnings_as_headers() {
+        let warning = Error::HaloWaypoint(HaloWaypointError::UnknownMissionId(999));
+        let res = with_warnings(
+            json(StatusCode::OK, &Vec::<ServiceRecordResource>::new()),
+            &[warning.clone(), warning],
+        );
+
+        assert_eq!(
+            res.headers()
+                .get_all(header::WARNING)
+                .iter()
+                .collect::<Vec<&HeaderValue>>(),
+            vec!["199 - \"Halo Waypoint returned unknown mission 999\""]
+        );
+    }
+
+    #[test]
+    fn openapi_describes_routes() {
+        let openapi = serde_json::from_str::<Value>(Service::OPENAPI).unwrap();
+        let paths = openapi["paths"].as_object().unwrap();
+
+        assert_eq!(
+            paths.keys().map(String::as_str).collect::<Vec<&str>>(),
+            vec![
+                "/api/games",
+                "/api/games/{gameId}/missions",
+                "/api/openapi.json",
+                "/api/players/{gamertag}/export.csv",
+                "/api/players/{gamertag}/games/{gameId}",
+                "/api/players/{gamertag}/games/{gameId}/missions/{missionId}",
+            ]
+        );
+    }
+}
@@ -1,91 +1,277 @@
 use futures::future::join_all;
+use itertools::{Either, Itertools};
+use std::sync::Arc;
 use time::Time;
+use tracing::{error, warn};
 
 use crate::campaign_modes::campaign_mode::CampaignMode;
 use crate::chainable::Chainable;
 use crate::difficulties::difficulty::Difficulty;
-use crate::error::Error;
-use crate::games::dao::{GamesDao, InMemoryGamesDao};
+use crate::error::{ConfigError, Error, HaloWaypointError};
+use crate::gamertags::gamertag::Gamertag;
+use crate::games::dao::GamesDao;
 use crate::games::game::{Game, GameId};
-use crate::halo_waypoint::client::{Client, InMemoryCacheClient};
+use crate::halo_waypoint::client::Client;
+use crate::halo_waypoint::freshness::Freshness;
+use crate::halo_waypoint::health::ClientHealth;
 use crate::halo_waypoint::requests::auth::GetAuthRequest;
 use crate::halo_waypoint::requests::service_record::{
     GetServiceRecordRequest, GetServiceRecordResponse,
 };
+use crate::locales::locale::Locale;
+use crate::localisations::dao::LocalisationsDao;
+use crate::missions::dao::MissionsDao;
 use crate::missions::mission::{Mission, MissionId};
+use crate::platforms::platform::Platform;
+use crate::service_records::export::ExportRow;
+use crate::service_records::fetched::Fetched;
 use crate::service_records::service_record::ServiceRecord;
 
 pub struct ServiceRecordsDao {
-    games_dao: Box<dyn GamesDao + Send + Sync>,
+    games_dao: Arc<dyn GamesDao + Send + Sync>,
+    missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+    localisations_dao: Arc<dyn LocalisationsDao + Send + Sync>,
+    credentials: Option<GetAuthRequest>,
     halo_waypoint: Box<dyn Client + Send + Sync>,
 }
 
 impl ServiceRecordsDao {
+    const LOGGED_BODY_CHARS: usize = 200;
+
+    pub fn new(
+        games_dao: Arc<dyn GamesDao + Send + Sync>,
+        missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+        localisations_dao: Arc<dyn LocalisationsDao + Send + Sync>,
+        credentials: Option<GetAuthRequest>,
+        halo_waypoint: Box<dyn Client + Send + Sync>,
+    ) -> Self {
+        Self {
+            games_dao,
+            missions_dao,
+            localisations_dao,
+            credentials,
+            halo_waypoint,
+        }
+    }
+
+    /// The player's service records for `game`. Missions missing from the catalogue are skipped
+    /// and reported as inconsistencies.
     pub async fn find_by_player_and_game(
         &self,
-        player: String,
+        player: Gamertag,
         game: Game,
-    ) -> Option<Vec<ServiceRecord>> {
-        let req = GetAuthRequest::default();
-        let auth = self
-            .halo_waypoint
-            .get_auth(&req)
-            .await
-            .map_err(|err| eprintln!("{:?}", err))
-            .ok()?;
+        platform: Platform,
+        locale: Locale,
+    ) -> Result<Fetched<Vec<ServiceRecord>>, Error> {
+        let req = self
+            .credentials
+            .as_ref()
+            .ok_or(Error::Config(ConfigError::MissingCredentials))
+            .map_err(Self::log)?;
+        let auth = self.halo_waypoint.get_auth(req).await.map_err(Self::log)?;
 
-        vec![CampaignMode::Solo, CampaignMode::Coop]
+        let reqs = vec![CampaignMode::Solo, CampaignMode::Coop]
             .into_iter()
             .map(|campaign_mode| {
-                let auth = auth.clone();
-                let req =
-                    GetServiceRecordRequest::from_internal(player.as_str(), &game, &campaign_mode);
-
-                async move { self.halo_waypoint.get_service_record(&auth, &req).await }
+                GetServiceRecordRequest::from_internal(
+                    &player,
+                    &game,
+                    &campaign_mode,
+                    &platform,
+                    &locale,
+                )
             })
+            .collect::<Result<Vec<GetServiceRecordRequest>, Error>>()
+            .map_err(Self::log)?;
+
+        let res = reqs
+            .iter()
+            .map(|req| self.halo_waypoint.get_service_record(&auth, req))
             .pipe(join_all)
-            .await
-            .into_iter()
+            .await;
+        // Looked up now the pages are cached, under the login that fetched them.
+        let freshness = reqs
+            .iter()
+            .map(|req| self.halo_waypoint.freshness(&auth, req))
+            .pipe(stalest);
+
+        res.into_iter()
             .collect::<Result<Vec<GetServiceRecordResponse>, Error>>()
             .map(|res| {
-                res.iter()
-                    .flat_map(GetServiceRecordResponse::to_internal)
-                    .collect::<Vec<(GameId, MissionId, CampaignMode, Difficulty, Time, i32)>>()
-                    .pipe(|runs| ServiceRecord::from_player_and_runs(&player, &runs))
+                let (runs, errors): (
+                    Vec<(GameId, MissionId, CampaignMode, Difficulty, Time, i32)>,
+                    Vec<Error>,
+                ) = res
+                    .iter()
+                    .flat_map(|res| res.to_internal(&*self.games_dao, &*self.missions_dao))
+                    .partition_map(|run| match run {
+                        Ok(run) => Either::Left(run),
+                        Err(err) => Either::Right(err),
+                    });
+
+                let inconsistencies = errors
+                    .into_iter()
+                    .chain(self.verify_mission_titles(&res, &locale))
+                    .collect::<Vec<Error>>();
+                inconsistencies
+                    .iter()
+                    .for_each(|err| warn!(error = ?err, "service record inconsistency"));
+
+                Fetched::new(
+                    ServiceRecord::from_player_and_runs(&player, &runs),
+                    inconsistencies,
+                    freshness,
+                )
             })
-            .map_err(|err| eprintln!("{:?}", err))
-            .ok()
+            .map_err(Self::log)
     }
 
     async fn find_by_player_and_game_id(
         &self,
-        player: String,
+        player: Gamertag,
         game_id: GameId,
-    ) -> Option<Vec<ServiceRecord>> {
-        let game = self.games_dao.find_by_id(game_id)?;
-        self.find_by_player_and_game(player, game).await
+        platform: Platform,
+        locale: Locale,
+    ) -> Result<Option<Fetched<Vec<ServiceRecord>>>, Error> {
+        match self.games_dao.find_by_id(game_id) {
+            Some(game) => self
+                .find_by_player_and_game(player, game, platform, locale)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
     }
 
+    /// The inconsistencies are those of the mission's game, unknown missions can't be told apart.
     pub async fn find_by_player_and_mission(
         &self,
-        player: String,
+        player: Gamertag,
         mission: &Mission,
-    ) -> Option<ServiceRecord> {
-        self.find_by_player_and_game_id(player.clone(), mission.game_id())
+        platform: Platform,
+        locale: Locale,
+    ) -> Result<Fetched<Option<ServiceRecord>>, Error> {
+        self.find_by_player_and_game_id(player.clone(), mission.game_id(), platform, locale)
             .await
-            .and_then(|service_records| {
-                service_records.into_iter().find(|service_record| {
-                    service_record.player() == player
-                        && service_record.game_id() == mission.game_id()
-                        && service_record.mission_id() == mission.id()
-                })
+            .map(|fetched| {
+                fetched
+                    .unwrap_or_else(|| Fetched::new(vec![], vec![], None))
+                    .map(|service_records| {
+                        service_records.into_iter().find(|service_record| {
+                            service_record.player() == player
+                                && service_record.game_id() == mission.game_id()
+                                && service_record.mission_id() == mission.id()
+                        })
+                    })
             })
     }
 
-    pub fn default() -> Self {
-        Self {
-            games_dao: Box::new(InMemoryGamesDao::default()),
-            halo_waypoint: Box::new(InMemoryCacheClient::default()),
+    /// Spreadsheet rows for every mission the player finished, games in menu order.
+    pub async fn export_by_player(
+        &self,
+        player: Gamertag,
+        games: Vec<Game>,
+        platform: Platform,
+        locale: Locale,
+    ) -> Result<Fetched<Vec<ExportRow>>, Error> {
+        let games = games
+            .into_iter()
+            .sorted_by_key(|game| game.menu_order())
+            .collect::<Vec<Game>>();
+        // The games are scraped together, join_all keeps them in menu order.
+        let fetched = games
+            .iter()
+            .map(|game| {
+                self.find_by_player_and_game(player.clone(), game.clone(), platform, locale.clone())
+            })
+            .pipe(join_all)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<Fetched<Vec<ServiceRecord>>>, Error>>()?;
+
+        let mut rows = vec![];
+        let mut inconsistencies = vec![];
+        let mut freshness = vec![];
+        for (game, fetched) in games.iter().zip(fetched) {
+            inconsistencies.extend(fetched.inconsistencies().to_vec());
+            freshness.push(fetched.freshness());
+
+            rows.extend(fetched.records().iter().flat_map(|service_record| {
+                self.missions_dao
+                    .find_by_game_id_and_id(service_record.game_id(), service_record.mission_id())
+                    .map(|mission| ExportRow::from_service_record(service_record, game, &mission))
+                    .unwrap_or_default()
+            }));
         }
+
+        Ok(Fetched::new(rows, inconsistencies, stalest(freshness)))
+    }
+
+    /// Flags scraped titles that disagree with the catalogue, a sign that Waypoint renumbered missions.
+    ///
+    /// Pages in other languages are checked against the localisation for `locale` and skipped
+    /// when it has no name for the mission.
+    fn verify_mission_titles(
+        &self,
+        res: &[GetServiceRecordResponse],
+        locale: &Locale,
+    ) -> Vec<Error> {
+        res.iter()
+            .flat_map(|res| res.mission_titles(&*self.games_dao, &*self.missions_dao))
+            .filter_map(|(game_id, mission_id, title)| {
+                let mission = self
+                    .missions_dao
+                    .find_by_game_id_and_id(game_id, mission_id);
+                let expected = self
+                    .localisations_dao
+                    .find_mission_name(game_id, mission_id, locale)
+                    .or_else(|| match &mission {
+                        Some(mission) if locale.language() == "en" => Some(mission.name()),
+                        _ => None,
+                    });
+
+                match (mission, expected) {
+                    (Some(_), Some(expected)) if Mission::names_match(&expected, &title) => None,
+                    (Some(_), None) => None,
+                    (_, expected) => Some(HaloWaypointError::MissionTitleMismatch {
+                        game_id: *game_id.value(),
+                        mission_id: *mission_id.value(),
+                        expected,
+                        actual: title,
+                    }),
+                }
+            })
+            .map(Error::HaloWaypoint)
+            .collect()
     }
+
+    pub fn health(&self) -> ClientHealth {
+        self.halo_waypoint.health(self.credentials.as_ref())
+    }
+
+    pub async fn flush(&self) {
+        self.halo_waypoint.flush().await
+    }
+
+    /// Unexpected upstream responses are whole HTML pages, only their start is logged.
+    fn log(err: Error) -> Error {
+        match &err {
+            Error::HaloWaypoint(HaloWaypointError::Http(body)) => error!(
+                body = %body.chars().take(Self::LOGGED_BODY_CHARS).collect::<String>(),
+                body_length = body.len(),
+                "service record request failed with an unexpected response"
+            ),
+            _ => error!(error = ?err, "service record request failed"),
+        }
+        err
+    }
+}
+
+/// A response built from several pages is fresh as long as the first to expire, and not at all
+/// when one of them isn't cached.
+fn stalest<I: IntoIterator<Item = Option<Freshness>>>(freshness: I) -> Option<Freshness> {
+    freshness
+        .into_iter()
+        .collect::<Option<Vec<Freshness>>>()?
+        .into_iter()
+        .fold1(Freshness::stalest)
 }
@@ -0,0 +1,195 @@
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::cli::format::Tabular;
+use crate::games::game::Game;
+use crate::missions::mission::Mission;
+use crate::service_records::service_record::ServiceRecord;
+
+/// One spreadsheet line: a player's best on a mission for one mode and difficulty, next to its par.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportRow {
+    game: String,
+    mission_id: i32,
+    mission: String,
+    campaign_mode: &'static str,
+    difficulty: &'static str,
+    best_time: String,
+    best_score: i32,
+    par_time: Option<String>,
+    par_score: Option<i32>,
+    beat_par_time: Option<bool>,
+    beat_par_score: Option<bool>,
+}
+
+impl ExportRow {
+    /// Compares against the current par; the beat flags are empty when the mission has no par.
+    pub fn from_service_record(
+        service_record: &ServiceRecord,
+        game: &Game,
+        mission: &Mission,
+    ) -> Vec<Self> {
+        let par_time = mission.par_time();
+        let par_score = mission.par_score();
+
+        service_record
+            .runs()
+            .into_iter()
+            .map(|run| ((run.campaign_mode(), run.difficulty()), run))
+            .into_group_map()
+            .into_iter()
+            .sorted_by_key(|(key, _)| *key)
+            .filter_map(|((campaign_mode, difficulty), runs)| {
+                let best_time = runs.iter().map(|run| run.time()).min()?;
+                let best_score = runs.iter().map(|run| run.score()).max()?;
+
+                Some(Self {
+                    game: game.short_code(),
+                    mission_id: *mission.id().value(),
+                    mission: mission.name(),
+                    campaign_mode: campaign_mode.into(),
+                    difficulty: difficulty.into(),
+                    best_time: best_time.format("%T"),
+                    best_score,
+                    par_time: par_time.map(|time| time.format("%T")),
+                    par_score,
+                    beat_par_time: par_time.map(|par_time| best_time <= par_time),
+                    beat_par_score: par_score.map(|par_score| best_score >= par_score),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Tabular for ExportRow {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "game",
+            "mission_id",
+            "mission",
+            "campaign_mode",
+            "difficulty",
+            "best_time",
+            "best_score",
+            "par_time",
+            "par_score",
+            "beat_par_time",
+            "beat_par_score",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let optional = |cell: Option<String>| cell.unwrap_or_else(|| "-".to_string());
+
+        vec![
+            self.game.clone(),
+            self.mission_id.to_string(),
+            self.mission.clone(),
+            self.campaign_mode.to_string(),
+            self.difficulty.to_string(),
+            self.best_time.clone(),
+            self.best_score.to_string(),
+            optional(self.par_time.clone()),
+            optional(self.par_score.map(|score| score.to_string())),
+            optional(self.beat_par_time.map(|beat| beat.to_string())),
+            optional(self.beat_par_score.map(|beat| beat.to_string())),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod export_row_test {
+    use super::*;
+    use crate::campaign_modes::campaign_mode::CampaignMode;
+    use crate::cli::format::Format;
+    use crate::difficulties::difficulty::Difficulty;
+    use crate::gamertags::gamertag::Gamertag;
+    use crate::games::game::GameId;
+    use crate::missions::mission::{MissionId, MissionKind};
+    use crate::missions::par::Par;
+    use crate::service_records::service_record::ServiceRecordRun;
+    use time::{date, time, Time};
+
+    fn rows() -> Vec<ExportRow> {
+        let game = Game::new(
+            GameId::new(1),
+            "Halo: Combat Evolved",
+            "haloce",
+            2001,
+            "Bungie",
+            10,
+            1,
+        );
+        let mission = Mission::new(
+            GameId::new(1),
+            MissionId::new(1),
+            "The Pillar of Autumn",
+            vec![Par::new(
+                "launch",
+                date!(2014 - 11 - 11),
+                Some(time!(00:15:00)),
+                Some(17_000),
+            )],
+            MissionKind::Level,
+            1,
+        );
+        let run = |campaign_mode, difficulty, time: Time, score| {
+            ServiceRecordRun::new(
+                GameId::new(1),
+                MissionId::new(1),
+                campaign_mode,
+                difficulty,
+                time,
+                score,
+            )
+        };
+        let service_record = ServiceRecord::new(
+            Gamertag::try_from_str("Master Chief").unwrap(),
+            GameId::new(1),
+            MissionId::new(1),
+            vec![
+                run(
+                    CampaignMode::Solo,
+                    Difficulty::Legendary,
+                    time!(00:15:53),
+                    23_520,
+                ),
+                run(CampaignMode::Solo, Difficulty::Easy, time!(00:09:12), 9_000),
+            ],
+        );
+
+        ExportRow::from_service_record(&service_record, &game, &mission)
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            Format::Csv.render(&rows()),
+            "game,mission_id,mission,campaign_mode,difficulty,best_time,best_score,par_time,par_score,beat_par_time,beat_par_score\n\
+             haloce,1,The Pillar of Autumn,solo,easy,00:09:12,9000,00:15:00,17000,true,false\n\
+             haloce,1,The Pillar of Autumn,solo,legendary,00:15:53,23520,00:15:00,17000,false,true\n"
+        );
+    }
+
+    #[test]
+    fn csv_without_rows() {
+        assert_eq!(
+            Format::Csv.render::<ExportRow>(&[]),
+            format!("{}\n", ExportRow::headers().join(","))
+        );
+    }
+
+    #[test]
+    fn cells_without_par() {
+        let row = ExportRow {
+            par_time: None,
+            par_score: None,
+            beat_par_time: None,
+            beat_par_score: None,
+            ..rows().remove(0)
+        };
+
+        assert_eq!(row.cells().len(), ExportRow::headers().len());
+        assert_eq!(&row.cells()[7..], ["-", "-", "-", "-"]);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error::Error;
+use crate::halo_waypoint::freshness::Freshness;
+
+/// Records read from Halo Waypoint pages, with the inconsistencies met in them and how fresh the
+/// cached pages were, `None` when one of them isn't cached.
+pub struct Fetched<T> {
+    records: T,
+    inconsistencies: Vec<Error>,
+    freshness: Option<Freshness>,
+}
+
+impl<T> Fetched<T> {
+    pub fn new(records: T, inconsistencies: Vec<Error>, freshness: Option<Freshness>) -> Self {
+        Self {
+            records,
+            inconsistencies,
+            freshness,
+        }
+    }
+
+    pub fn records(&self) -> &T {
+        &self.records
+    }
+
+    pub fn inconsistencies(&self) -> &[Error] {
+        &self.inconsistencies
+    }
+
+    pub fn freshness(&self) -> Option<Freshness> {
+        self.freshness
+    }
+
+    pub fn into_records(self) -> T {
+        self.records
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Fetched<U> {
+        Fetched {
+            records: f(self.records),
+            inconsistencies: self.inconsistencies,
+            freshness: self.freshness,
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod dao;
+pub mod export;
+pub mod fetched;
 pub mod service_record;
@@ -5,12 +5,13 @@ use std::cmp::Ordering;
 
 use crate::campaign_modes::campaign_mode::CampaignMode;
 use crate::difficulties::difficulty::Difficulty;
+use crate::gamertags::gamertag::Gamertag;
 use crate::games::game::GameId;
 use crate::missions::mission::MissionId;
 
 #[derive(PartialEq, Eq)]
 pub struct ServiceRecord {
-    player: String,
+    player: Gamertag,
     game_id: GameId,
     mission_id: MissionId,
     runs: Vec<ServiceRecordRun>,
@@ -18,7 +19,7 @@ pub struct ServiceRecord {
 
 impl ServiceRecord {
     pub fn new(
-        player: String,
+        player: Gamertag,
         game_id: GameId,
         mission_id: MissionId,
         runs: Vec<ServiceRecordRun>,
@@ -31,7 +32,7 @@ impl ServiceRecord {
         }
     }
 
-    pub fn player(&self) -> String {
+    pub fn player(&self) -> Gamertag {
         self.player.clone()
     }
 
@@ -47,8 +48,27 @@ impl ServiceRecord {
         self.runs.clone()
     }
 
+    /// Best time and score over runs at `difficulty_at_least` or harder, which need not come from the same run.
+    pub fn best(
+        &self,
+        difficulty_at_least: Difficulty,
+        campaign_mode: CampaignMode,
+    ) -> Option<ServiceRecordBest> {
+        let runs = self
+            .runs
+            .iter()
+            .filter(|run| run.campaign_mode == campaign_mode)
+            .filter(|run| run.difficulty >= difficulty_at_least)
+            .collect::<Vec<&ServiceRecordRun>>();
+
+        let time = runs.iter().map(|run| run.time).min()?;
+        let score = runs.iter().map(|run| run.score).max()?;
+
+        Some(ServiceRecordBest::new(time, score))
+    }
+
     pub fn from_player_and_runs(
-        player: &str,
+        player: &Gamertag,
         runs: &[(GameId, MissionId, CampaignMode, Difficulty, Time, i32)],
     ) -> Vec<Self> {
         runs.iter()
@@ -58,10 +78,12 @@ impl ServiceRecord {
             .map(|((game_id, mission_id), runs)| {
                 let runs = runs
                     .into_iter()
-                    .map(|(c, d, t, s)| ServiceRecordRun::new(*c, *d, *t, *s))
+                    .map(|(c, d, t, s)| {
+                        ServiceRecordRun::new(*game_id, *mission_id, *c, *d, *t, *s)
+                    })
                     .collect();
 
-                Self::new(player.to_string(), *game_id, *mission_id, runs)
+                Self::new(player.clone(), *game_id, *mission_id, runs)
             })
             .sorted()
             .collect()
@@ -82,6 +104,8 @@ impl PartialOrd for ServiceRecord {
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct ServiceRecordRun {
+    game_id: GameId,
+    mission_id: MissionId,
     campaign_mode: CampaignMode,
     difficulty: Difficulty,
     time: Time,
@@ -90,12 +114,16 @@ pub struct ServiceRecordRun {
 
 impl ServiceRecordRun {
     pub fn new(
+        game_id: GameId,
+        mission_id: MissionId,
         campaign_mode: CampaignMode,
         difficulty: Difficulty,
         time: Time,
         score: i32,
     ) -> Self {
         Self {
+            game_id,
+            mission_id,
             campaign_mode,
             difficulty,
             time,
@@ -103,6 +131,14 @@ impl ServiceRecordRun {
         }
     }
 
+    pub fn game_id(&self) -> GameId {
+        self.game_id
+    }
+
+    pub fn mission_id(&self) -> MissionId {
+        self.mission_id
+    }
+
     pub fn campaign_mode(&self) -> CampaignMode {
         self.campaign_mode
     }
@@ -119,3 +155,104 @@ impl ServiceRecordRun {
         self.score
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServiceRecordBest {
+    time: Time,
+    score: i32,
+}
+
+impl ServiceRecordBest {
+    pub fn new(time: Time, score: i32) -> Self {
+        Self { time, score }
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod service_record_test {
+    use super::*;
+    use time::time;
+
+    fn service_record() -> ServiceRecord {
+        let run = |campaign_mode, difficulty, time, score| {
+            ServiceRecordRun::new(
+                GameId::new(1),
+                MissionId::new(1),
+                campaign_mode,
+                difficulty,
+                time,
+                score,
+            )
+        };
+
+        ServiceRecord::new(
+            Gamertag::try_from_str("Master Chief").unwrap(),
+            GameId::new(1),
+            MissionId::new(1),
+            vec![
+                run(
+                    CampaignMode::Solo,
+                    Difficulty::Easy,
+                    time!(00:03:00),
+                    20_000,
+                ),
+                run(
+                    CampaignMode::Solo,
+                    Difficulty::Heroic,
+                    time!(00:05:00),
+                    9_000,
+                ),
+                run(
+                    CampaignMode::Solo,
+                    Difficulty::Legendary,
+                    time!(00:04:30),
+                    12_000,
+                ),
+                run(
+                    CampaignMode::Coop,
+                    Difficulty::Legendary,
+                    time!(00:04:00),
+                    15_000,
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn best_counts_harder_difficulties() {
+        assert_eq!(
+            service_record().best(Difficulty::Heroic, CampaignMode::Solo),
+            Some(ServiceRecordBest::new(time!(00:04:30), 12_000))
+        );
+        assert_eq!(
+            service_record().best(Difficulty::Easy, CampaignMode::Solo),
+            Some(ServiceRecordBest::new(time!(00:03:00), 20_000))
+        );
+    }
+
+    #[test]
+    fn best_by_campaign_mode() {
+        assert_eq!(
+            service_record().best(Difficulty::Heroic, CampaignMode::Coop),
+            Some(ServiceRecordBest::new(time!(00:04:00), 15_000))
+        );
+        assert_eq!(
+            ServiceRecord::new(
+                Gamertag::try_from_str("Master Chief").unwrap(),
+                GameId::new(1),
+                MissionId::new(1),
+                vec![],
+            )
+            .best(Difficulty::Easy, CampaignMode::Solo),
+            None
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use itertools::{Either, Itertools};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use time::Time;
+
+use crate::campaign_modes::campaign_mode::CampaignMode;
+use crate::chainable::Chainable;
+use crate::difficulties::difficulty::Difficulty;
+use crate::error::{CatalogueError, Error};
+use crate::games::game::GameId;
+use crate::missions::dao::MissionsDao;
+use crate::missions::mission::MissionId;
+use crate::service_records::service_record::ServiceRecordRun;
+use crate::world_records::world_record::WorldRecord;
+
+pub trait WorldRecordsDao {
+    fn find_by_run(&self, run: &ServiceRecordRun) -> Option<WorldRecord>;
+}
+
+#[derive(Deserialize)]
+struct WorldRecordRow {
+    game_id: i32,
+    mission_id: i32,
+    difficulty: String,
+    campaign_mode: String,
+    time: String,
+}
+
+#[derive(Clone)]
+pub struct InMemoryWorldRecordsDao {
+    world_records: Vec<WorldRecord>,
+    missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+}
+
+impl InMemoryWorldRecordsDao {
+    pub const DEFAULT: &'static str = include_str!("../../resources/world_records.csv");
+
+    pub fn new(
+        world_records: Vec<WorldRecord>,
+        missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+    ) -> Self {
+        Self {
+            world_records,
+            missions_dao,
+        }
+    }
+
+    /// Parses the CSV we maintain, rejecting rows for missions the catalogue doesn't know.
+    pub fn try_from_csv(
+        csv: &str,
+        missions_dao: Arc<dyn MissionsDao + Send + Sync>,
+    ) -> Result<Self, Error> {
+        let rows = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes())
+            .deserialize::<WorldRecordRow>()
+            .collect::<Result<Vec<WorldRecordRow>, csv::Error>>()
+            .map_err(|err| CatalogueError::Parse(err.to_string()).pipe(Error::Catalogue))?;
+
+        let mut seen = HashSet::new();
+        let (world_records, errors): (Vec<WorldRecord>, Vec<CatalogueError>) = rows
+            .iter()
+            .map(|row| {
+                let game_id = GameId::new(row.game_id);
+                let mission_id = MissionId::new(row.mission_id);
+                let world_record = match (
+                    Self::parse_difficulty(&row.difficulty),
+                    Self::parse_campaign_mode(&row.campaign_mode),
+                    Time::parse(&row.time, "%T").ok(),
+                ) {
+                    (Some(difficulty), Some(campaign_mode), Some(time)) => {
+                        WorldRecord::new(game_id, mission_id, difficulty, campaign_mode, time)
+                    }
+                    _ => {
+                        return Err(CatalogueError::InvalidWorldRecord(format!(
+                            "{},{},{},{},{}",
+                            row.game_id,
+                            row.mission_id,
+                            row.difficulty,
+                            row.campaign_mode,
+                            row.time
+                        )))
+                    }
+                };
+
+                if missions_dao
+                    .find_by_game_id_and_id(game_id, mission_id)
+                    .is_none()
+                {
+                    return Err(CatalogueError::MissingMission(row.game_id, row.mission_id));
+                }
+
+                let key = (
+                    game_id,
+                    mission_id,
+                    world_record.difficulty(),
+                    world_record.campaign_mode(),
+                );
+                if !seen.insert(key) {
+                    return Err(CatalogueError::DuplicateWorldRecord(
+                        row.game_id,
+                        row.mission_id,
+                    ));
+                }
+
+                Ok(world_record)
+            })
+            .partition_map(|world_record| match world_record {
+                Ok(world_record) => Either::Left(world_record),
+                Err(err) => Either::Right(err),
+            });
+
+        if !errors.is_empty() {
+            return errors
+                .into_iter()
+                .map(Error::Catalogue)
+                .collect::<Vec<Error>>()
+                .pipe(Error::List)
+                .pipe(Err);
+        }
+
+        Ok(Self::new(world_records, missions_dao))
+    }
+
+    fn parse_difficulty(difficulty: &str) -> Option<Difficulty> {
+        match difficulty {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "heroic" => Some(Difficulty::Heroic),
+            "legendary" => Some(Difficulty::Legendary),
+            _ => None,
+        }
+    }
+
+    fn parse_campaign_mode(campaign_mode: &str) -> Option<CampaignMode> {
+        match campaign_mode {
+            "solo" => Some(CampaignMode::Solo),
+            "coop" => Some(CampaignMode::Coop),
+            _ => None,
+        }
+    }
+}
+
+impl WorldRecordsDao for InMemoryWorldRecordsDao {
+    /// Records for missions a reloaded catalogue removed or renumbered are no longer served.
+    fn find_by_run(&self, run: &ServiceRecordRun) -> Option<WorldRecord> {
+        self.missions_dao
+            .find_by_game_id_and_id(run.game_id(), run.mission_id())?;
+
+        self.world_records
+            .iter()
+            .find(|world_record| {
+                world_record.game_id() == run.game_id()
+                    && world_record.mission_id() == run.mission_id()
+                    && world_record.difficulty() == run.difficulty()
+                    && world_record.campaign_mode() == run.campaign_mode()
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod in_memory_world_records_dao_test {
+    use super::*;
+    use crate::catalogues::catalogue::Catalogue;
+    use time::time;
+
+    #[test]
+    fn default() {
+        let catalogue = Catalogue::default();
+
+        assert!(InMemoryWorldRecordsDao::try_from_csv(
+            InMemoryWorldRecordsDao::DEFAULT,
+            Arc::new(catalogue.missions_dao().clone())
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn find_by_run() {
+        let catalogue = Catalogue::default();
+        let dao = InMemoryWorldRecordsDao::try_from_csv(
+            "game_id,mission_id,difficulty,campaign_mode,time\n\
+             1,1,legendary,solo,00:04:11\n\
+             1,1,easy,coop,00:03:58\n",
+            Arc::new(catalogue.missions_dao().clone()),
+        )
+        .unwrap();
+
+        let run = ServiceRecordRun::new(
+            GameId::new(1),
+            MissionId::new(1),
+            CampaignMode::Solo,
+            Difficulty::Legendary,
+            time!(00:05:00),
+            0,
+        );
+
+        assert_eq!(
+            dao.find_by_run(&run)
+                .map(|world_record| world_record.time()),
+            Some(time!(00:04:11))
+        );
+    }
+
+    #[test]
+    fn find_by_run_missing_mission() {
+        let catalogue = Catalogue::try_from_str(
+            r#"
+            [[games]]
+            id = 1
+            name = "Halo"
+            short_code = "haloce"
+            release_year = 2001
+            developer = "Bungie"
+            halo_waypoint_key = "HaloCombatEvolved"
+            menu_order = 2
+            "#,
+        )
+        .unwrap();
+        let dao = InMemoryWorldRecordsDao::new(
+            vec![WorldRecord::new(
+                GameId::new(1),
+                MissionId::new(1),
+                Difficulty::Legendary,
+                CampaignMode::Solo,
+                time!(00:04:11),
+            )],
+            Arc::new(catalogue.missions_dao().clone()),
+        );
+
+        let run = ServiceRecordRun::new(
+            GameId::new(1),
+            MissionId::new(1),
+            CampaignMode::Solo,
+            Difficulty::Legendary,
+            time!(00:05:00),
+            0,
+        );
+
+        assert_eq!(dao.find_by_run(&run), None);
+    }
+
+    #[test]
+    fn invalid_rows() {
+        let catalogue = Catalogue::default();
+        let res = InMemoryWorldRecordsDao::try_from_csv(
+            "game_id,mission_id,difficulty,campaign_mode,time\n\
+             1,1,mythic,solo,00:04:11\n\
+             1,99,legendary,solo,00:04:11\n\
+             1,2,legendary,solo,00:04:11\n\
+             1,2,legendary,solo,00:04:09\n",
+            Arc::new(catalogue.missions_dao().clone()),
+        );
+
+        assert!(matches!(
+            res,
+            Err(Error::List(errors)) if matches!(errors.as_slice(), [
+                Error::Catalogue(CatalogueError::InvalidWorldRecord(_)),
+                Error::Catalogue(CatalogueError::MissingMission(1, 99)),
+                Error::Catalogue(CatalogueError::DuplicateWorldRecord(1, 2)),
+            ])
+        ));
+    }
+}
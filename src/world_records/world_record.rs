@@ -0,0 +1,53 @@
+use time::Time;
+
+use crate::campaign_modes::campaign_mode::CampaignMode;
+use crate::difficulties::difficulty::Difficulty;
+use crate::games::game::GameId;
+use crate::missions::mission::MissionId;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldRecord {
+    game_id: GameId,
+    mission_id: MissionId,
+    difficulty: Difficulty,
+    campaign_mode: CampaignMode,
+    time: Time,
+}
+
+impl WorldRecord {
+    pub fn new(
+        game_id: GameId,
+        mission_id: MissionId,
+        difficulty: Difficulty,
+        campaign_mode: CampaignMode,
+        time: Time,
+    ) -> Self {
+        Self {
+            game_id,
+            mission_id,
+            difficulty,
+            campaign_mode,
+            time,
+        }
+    }
+
+    pub fn game_id(&self) -> GameId {
+        self.game_id
+    }
+
+    pub fn mission_id(&self) -> MissionId {
+        self.mission_id
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn campaign_mode(&self) -> CampaignMode {
+        self.campaign_mode
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+}